@@ -0,0 +1,58 @@
+//! Backing for vXLM deposited into and withdrawn from the contract.
+//!
+//! `deposit` and `withdraw` move the backing asset through a [`CollateralBackend`].
+//! For now that is [`InternalLedger`], which only keeps the running total in
+//! storage; a token contract (SAC) backend can implement the same trait later.
+
+use soroban_sdk::{Address, Env};
+
+use crate::errors::ContractError;
+use crate::types::DataKey;
+
+/// Moves the asset that backs vXLM in and out of the contract
+pub trait CollateralBackend {
+    /// Takes `amount` of the backing asset from `from` into the contract
+    fn pull(env: &Env, from: &Address, amount: i128) -> Result<(), ContractError>;
+
+    /// Sends `amount` of the backing asset from the contract to `to`
+    fn push(env: &Env, to: &Address, amount: i128) -> Result<(), ContractError>;
+}
+
+/// Virtual backend: no asset moves, only the collateral total is tracked
+pub struct InternalLedger;
+
+impl CollateralBackend for InternalLedger {
+    fn pull(env: &Env, _from: &Address, amount: i128) -> Result<(), ContractError> {
+        let collateral = total(env)
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Collateral, &collateral);
+
+        Ok(())
+    }
+
+    fn push(env: &Env, _to: &Address, amount: i128) -> Result<(), ContractError> {
+        let collateral = total(env);
+        if collateral < amount {
+            return Err(ContractError::InsufficientCollateral);
+        }
+        let collateral = collateral
+            .checked_sub(amount)
+            .ok_or(ContractError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Collateral, &collateral);
+
+        Ok(())
+    }
+}
+
+/// Total backing asset currently held for deposited vXLM
+pub fn total(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Collateral)
+        .unwrap_or(0)
+}
@@ -1,11 +1,125 @@
 //! Core contract implementation for the XLM Price Prediction Market.
 
-use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Map, Vec};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, Address, Bytes, Env, Map, Symbol, TryFromVal, Val, Vec,
+    U256,
+};
 
+use crate::collateral::{self, CollateralBackend, InternalLedger};
 use crate::errors::ContractError;
 use crate::types::{
-    BetSide, DataKey, OraclePayload, PrecisionPrediction, Round, RoundMode, UserPosition, UserStats,
+    AccountSnapshot, BetChoice, BetRecord, BetSide, ConfigKey, CurrentPosition, DataKey, Defaults,
+    LeaderboardEntry, LegacyUserPosition, LockedGrant, OraclePayload, Payout, PayoutExplanation,
+    PayoutKind, PrecisionPrediction, ProtocolStats, Round, RoundMode, RoundPayouts, RoundResult,
+    RoundStatus, ScheduledRound, Settlement, SettlementOutcome, UserPosition, UserStats,
 };
+#[cfg(feature = "testnet")]
+use crate::types::{RoundState, StateDump};
+
+/// Maximum number of oracle price checkpoints kept per round
+const MAX_CHECKPOINTS: u32 = 32;
+
+/// Minimum number of checkpoints required to resolve a round by TWAP
+const MIN_TWAP_CHECKPOINTS: u32 = 2;
+
+/// Precision payout modes: closest guess(es) take the pot, every guess
+/// earns a share weighted by stake / (1 + distance), the closest guess takes
+/// the pot with ties broken by a stake-weighted draw, or every guess in the
+/// final price's zone shares the pot by stake
+const PRECISION_PAYOUT_CLOSEST: u32 = 0;
+const PRECISION_PAYOUT_INVERSE_DISTANCE: u32 = 1;
+const PRECISION_PAYOUT_CLOSEST_DRAW: u32 = 2;
+const PRECISION_PAYOUT_ZONE: u32 = 3;
+
+/// Most zone boundaries set_precision_zones accepts
+const MAX_PRECISION_ZONES: u32 = 20;
+
+/// How far a Precision guess is from the final price: absolute stroops, or
+/// basis points of the final price
+const PRECISION_DISTANCE_ABSOLUTE: u32 = 0;
+const PRECISION_DISTANCE_PERCENT: u32 = 1;
+
+/// What happens to an Up/Down round with stakes on only one side: the usual
+/// settlement, a refund minus the house fee, or the pot carried into a later round
+const ONE_SIDED_REFUND: u32 = 0;
+const ONE_SIDED_VOID_WITH_FEE: u32 = 1;
+const ONE_SIDED_ROLLOVER: u32 = 2;
+
+/// How Up/Down winners' shares of the losing pool are rounded: down to the
+/// stroop, or to the nearest stroop with any overshoot taken back from the last winners
+const ROUNDING_FLOOR: u32 = 0;
+const ROUNDING_NEAREST: u32 = 1;
+
+/// Where the part of a Precision payout above the cap goes: the fee treasury,
+/// or the rollover pot paid to a later round's winners
+const PAYOUT_CAP_TREASURY: u32 = 0;
+const PAYOUT_CAP_ROLLOVER: u32 = 1;
+
+/// Fixed-point scale for inverse-distance weights
+const WEIGHT_SCALE: i128 = 1_000_000;
+
+/// Maximum number of round ids kept in a user's round history
+const MAX_USER_ROUNDS: u32 = 50;
+
+/// Number of players ranked on each season's leaderboard
+const LEADERBOARD_SIZE: u32 = 10;
+
+/// Shortest bet window accepted by set_windows unless configured otherwise
+const DEFAULT_MIN_BET_LEDGERS: u32 = 2;
+
+/// Longest allowed run window, so a round cannot be configured to never end
+const MAX_RUN_LEDGERS: u32 = 100_000;
+
+/// Bet and run windows in ledgers until set_windows is called
+pub(crate) const DEFAULT_BET_LEDGERS: u32 = 6;
+pub(crate) const DEFAULT_RUN_LEDGERS: u32 = 12;
+
+/// vXLM granted to each user by mint_initial
+pub(crate) const INITIAL_MINT: i128 = 1000_0000000;
+
+/// Oracle data older than this many seconds is rejected as stale
+pub(crate) const ORACLE_MAX_AGE_SECONDS: u64 = 300;
+
+/// Approximate ledger close time, used for the advisory round timestamps
+const SECONDS_PER_LEDGER: u64 = 5;
+
+/// Most round results returned by one get_round_results_page call
+const MAX_RESULTS_PAGE: u32 = 50;
+
+/// Most a create_round start price may differ from the latest checkpoint when
+/// fresh start prices are required, in basis points of the checkpoint price
+const START_PRICE_TOLERANCE_BPS: u128 = 100;
+
+/// Round results kept when no history limit has been set
+const DEFAULT_HISTORY_LIMIT: u32 = 1000;
+
+/// Most old round results removed by one resolution, so lowering the limit stays cheap
+const MAX_HISTORY_PRUNE: u32 = 50;
+
+/// Field names of the current Round schema, checked by get_active_round_safe
+const ROUND_FIELDS: [&str; 12] = [
+    "price_start",
+    "start_ledger",
+    "bet_end_ledger",
+    "end_ledger",
+    "pool_up",
+    "pool_down",
+    "mode",
+    "created_timestamp",
+    "bet_end_timestamp",
+    "end_timestamp",
+    "resolved",
+    "final_price",
+];
+
+/// Most distinct predicted prices returned by get_precision_landscape
+const MAX_LANDSCAPE_POINTS: u32 = 100;
+
+/// Longest current streaks kept in the streak index, and so returned by get_streaks_above
+const MAX_STREAK_RESULTS: u32 = 50;
+
+/// Where deposit/withdraw move the asset backing vXLM
+type Collateral = InternalLedger;
 
 #[contract]
 pub struct VirtualTokenContract;
@@ -20,16 +134,21 @@ impl VirtualTokenContract {
             return Err(ContractError::AlreadyInitialized);
         }
 
+        // Separation of duties: one key must not both create and resolve rounds
+        if admin == oracle {
+            return Err(ContractError::AdminOracleConflict);
+        }
+
         env.storage().persistent().set(&DataKey::Admin, &admin);
         env.storage().persistent().set(&DataKey::Oracle, &oracle);
 
         // Set default window values
         env.storage()
             .persistent()
-            .set(&DataKey::BetWindowLedgers, &6u32);
+            .set(&DataKey::BetWindowLedgers, &DEFAULT_BET_LEDGERS);
         env.storage()
             .persistent()
-            .set(&DataKey::RunWindowLedgers, &12u32);
+            .set(&DataKey::RunWindowLedgers, &DEFAULT_RUN_LEDGERS);
 
         Ok(())
     }
@@ -45,46 +164,125 @@ impl VirtualTokenContract {
             return Err(ContractError::InvalidPrice);
         }
 
-        // Default to Up/Down mode (0) if not specified
-        let mode_value = mode.unwrap_or(0);
+        let mode_value = Self::_mode_value(&env, mode)?;
+
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        Self::_open_round(&env, start_price, mode_value)
+    }
+
+    /// Rejects a start price more than START_PRICE_TOLERANCE_BPS away from the latest checkpoint
+    /// The checkpoint itself must be within the oracle's freshness window
+    fn _check_start_price(env: &Env, start_price: u128) -> Result<(), ContractError> {
+        let (checkpoint_price, checkpoint_timestamp) =
+            Self::get_last_checkpoint(env.clone()).ok_or(ContractError::StartPriceStale)?;
+        Self::_check_oracle_freshness(env, checkpoint_timestamp)
+            .map_err(|_| ContractError::StartPriceStale)?;
+
+        let deviation = start_price
+            .abs_diff(checkpoint_price)
+            .checked_mul(10_000)
+            .ok_or(ContractError::Overflow)?;
+        let allowed = checkpoint_price
+            .checked_mul(START_PRICE_TOLERANCE_BPS)
+            .ok_or(ContractError::Overflow)?;
+        if deviation > allowed {
+            return Err(ContractError::StartPriceStale);
+        }
+
+        Ok(())
+    }
+
+    /// Requires every new round's start price to be within 1% of the latest checkpoint (admin only)
+    /// Applies to create_round and activate_scheduled_round; the oracle can push the
+    /// checkpoint with push_checkpoint while no round is open
+    pub fn set_require_fresh_start_price(env: Env, enabled: bool) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::RequireFreshStartPrice, &enabled);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("fresh_px")),
+            enabled,
+        );
+
+        Ok(())
+    }
+
+    /// Returns true if new rounds check their start price against the latest checkpoint
+    pub fn get_require_fresh_start_price(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::RequireFreshStartPrice)
+            .unwrap_or(false)
+    }
+
+    /// Resolves an optional mode argument to 0 (Up/Down) or 1 (Precision)
+    /// Falls back to the configured default mode (Up/Down unless changed)
+    fn _mode_value(env: &Env, mode: Option<u32>) -> Result<u32, ContractError> {
+        let mode_value = mode.unwrap_or_else(|| Self::get_default_mode(env.clone()));
 
         // Validate mode is either 0 or 1
         if mode_value > 1 {
             return Err(ContractError::InvalidMode);
         }
 
+        Ok(mode_value)
+    }
+
+    /// Starts a round at the current ledger using the configured windows
+    /// Shared by create_round and activate_scheduled_round; callers check authorization
+    fn _open_round(env: &Env, start_price: u128, mode_value: u32) -> Result<(), ContractError> {
         let round_mode = if mode_value == 0 {
             RoundMode::UpDown
         } else {
             RoundMode::Precision
         };
 
-        let admin: Address = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Admin)
-            .ok_or(ContractError::AdminNotSet)?;
-
-        admin.require_auth();
-
         // Prevent overwriting an already active round
         if env.storage().persistent().has(&DataKey::ActiveRound) {
             return Err(ContractError::RoundAlreadyActive);
         }
 
-        // Get configured windows (with defaults)
-        let bet_ledgers: u32 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::BetWindowLedgers)
-            .unwrap_or(6);
-        let run_ledgers: u32 = env
+        // Positions of a batched resolution must all be paid first
+        if env.storage().persistent().has(&DataKey::Settlement) {
+            return Err(ContractError::SettlementInProgress);
+        }
+
+        // Enforce the configured gap since the last resolution
+        let cooldown = Self::get_round_cooldown(env.clone());
+        if let Some(last_resolved) = env
             .storage()
             .persistent()
-            .get(&DataKey::RunWindowLedgers)
-            .unwrap_or(12);
+            .get::<_, u32>(&DataKey::LastResolvedLedger)
+        {
+            if env.ledger().sequence() < last_resolved.saturating_add(cooldown) {
+                return Err(ContractError::CooldownActive);
+            }
+        }
+
+        // Scheduled rounds are checked when they open, against the checkpoint at that time
+        if Self::get_require_fresh_start_price(env.clone()) {
+            Self::_check_start_price(env, start_price)?;
+        }
 
+        // Ids are start ledgers: a round cancelled this ledger has used up this one
         let start_ledger = env.ledger().sequence();
+        if Self::get_last_round_id(env.clone()).is_some_and(|last| start_ledger <= last) {
+            return Err(ContractError::RoundIdTaken);
+        }
+
+        let (bet_ledgers, run_ledgers) = Self::_window_ledgers(env);
+
         let bet_end_ledger = start_ledger
             .checked_add(bet_ledgers)
             .ok_or(ContractError::Overflow)?;
@@ -92,6 +290,7 @@ impl VirtualTokenContract {
             .checked_add(run_ledgers)
             .ok_or(ContractError::Overflow)?;
 
+        let created_timestamp = env.ledger().timestamp();
         let round = Round {
             price_start: start_price,
             start_ledger,
@@ -100,11 +299,22 @@ impl VirtualTokenContract {
             pool_up: 0,
             pool_down: 0,
             mode: round_mode.clone(),
+            created_timestamp,
+            bet_end_timestamp: Self::_ledger_timestamp(created_timestamp, bet_ledgers),
+            end_timestamp: Self::_ledger_timestamp(created_timestamp, run_ledgers),
+            resolved: false,
+            final_price: 0,
         };
 
         env.storage()
             .persistent()
             .set(&DataKey::ActiveRound, &round);
+        env.storage()
+            .persistent()
+            .set(&DataKey::LastRoundId, &start_ledger);
+
+        // A new round replaces any previous round's results
+        env.storage().persistent().remove(&DataKey::ResolvedRound);
 
         // Clear previous round's positions based on mode
         env.storage().persistent().remove(&DataKey::UpDownPositions);
@@ -122,654 +332,5151 @@ impl VirtualTokenContract {
         Ok(())
     }
 
-    /// Returns the currently active round, if any
-    pub fn get_active_round(env: Env) -> Option<Round> {
-        env.storage().persistent().get(&DataKey::ActiveRound)
-    }
-
-    pub fn get_admin(env: Env) -> Option<Address> {
-        env.storage().persistent().get(&DataKey::Admin)
-    }
-
-    pub fn get_oracle(env: Env) -> Option<Address> {
-        env.storage().persistent().get(&DataKey::Oracle)
-    }
+    /// Cancels the active round and refunds every stake to pending winnings (admin only)
+    /// The round doesn't count as resolved, but its id stays used: the next round gets a later one
+    pub fn cancel_round(env: Env) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
 
-    /// Sets the betting and execution windows (admin only)
-    /// bet_ledgers: Number of ledgers users can place bets
-    /// run_ledgers: Total number of ledgers before round can be resolved
-    pub fn set_windows(env: Env, bet_ledgers: u32, run_ledgers: u32) -> Result<(), ContractError> {
-        let admin: Address = env
+        let round: Round = env
             .storage()
             .persistent()
-            .get(&DataKey::Admin)
-            .ok_or(ContractError::AdminNotSet)?;
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
 
-        admin.require_auth();
+        Self::_cancel_round(&env, &round)
+    }
 
-        // Validate both values are positive
-        if bet_ledgers == 0 || run_ledgers == 0 {
-            return Err(ContractError::InvalidDuration);
+    /// Refunds every stake in round to pending winnings and clears it with its positions
+    fn _cancel_round(env: &Env, round: &Round) -> Result<(), ContractError> {
+        let mut payouts = Vec::new(env);
+        match round.mode {
+            RoundMode::UpDown => {
+                let positions = Self::_split_stale_positions(
+                    round,
+                    Self::get_updown_positions(env.clone()),
+                    &mut payouts,
+                );
+                Self::_record_refunds(round, positions, &mut payouts)?
+            }
+            RoundMode::Precision => Self::_record_precision_refunds(
+                &Self::get_precision_predictions(env.clone()),
+                &mut payouts,
+            ),
         }
-
-        // Validate bet window closes before run window ends
-        if bet_ledgers >= run_ledgers {
-            return Err(ContractError::InvalidDuration);
+        for payout in payouts.iter() {
+            Self::_apply_payout(env, &payout, round.start_ledger)?;
         }
 
+        env.storage().persistent().remove(&DataKey::ActiveRound);
+        env.storage().persistent().remove(&DataKey::Positions);
+        env.storage().persistent().remove(&DataKey::UpDownPositions);
         env.storage()
             .persistent()
-            .set(&DataKey::BetWindowLedgers, &bet_ledgers);
-        env.storage()
-            .persistent()
-            .set(&DataKey::RunWindowLedgers, &run_ledgers);
+            .remove(&DataKey::PrecisionPositions);
 
-        // Emit event
         #[allow(deprecated)]
         env.events().publish(
-            (symbol_short!("windows"), symbol_short!("updated")),
-            (bet_ledgers, run_ledgers),
+            (symbol_short!("round"), symbol_short!("cancelled")),
+            round.start_ledger,
         );
 
         Ok(())
     }
 
-    /// Returns user statistics (wins, losses, streaks)
-    pub fn get_user_stats(env: Env, user: Address) -> UserStats {
-        let key = DataKey::UserStats(user);
-        env.storage().persistent().get(&key).unwrap_or(UserStats {
-            total_wins: 0,
-            total_losses: 0,
-            current_streak: 0,
-            best_streak: 0,
-        })
-    }
-
-    /// Returns user's claimable winnings
-    pub fn get_pending_winnings(env: Env, user: Address) -> i128 {
-        let key = DataKey::PendingWinnings(user);
-        env.storage().persistent().get(&key).unwrap_or(0)
+    /// Returns the id (start ledger) of the most recently opened round, resolved or cancelled
+    pub fn get_last_round_id(env: Env) -> Option<u32> {
+        env.storage().persistent().get(&DataKey::LastRoundId)
     }
 
-    /// Places a bet on the active round (Up/Down mode only)
-    pub fn place_bet(
+    /// Registers a round to start at start_ledger without opening it yet (admin only)
+    /// Replaces any earlier schedule; anyone can open it with activate_scheduled_round
+    pub fn schedule_round(
         env: Env,
-        user: Address,
-        amount: i128,
-        side: BetSide,
+        start_price: u128,
+        mode: Option<u32>,
+        start_ledger: u32,
     ) -> Result<(), ContractError> {
-        user.require_auth();
+        Self::_require_admin(&env)?;
 
-        if amount <= 0 {
-            return Err(ContractError::InvalidBetAmount);
+        if start_price == 0 {
+            return Err(ContractError::InvalidPrice);
         }
+        let mode_value = Self::_mode_value(&env, mode)?;
 
-        let mut round: Round = env
+        if start_ledger < env.ledger().sequence() {
+            return Err(ContractError::InvalidDuration);
+        }
+
+        env.storage().persistent().set(
+            &DataKey::ScheduledRound,
+            &ScheduledRound {
+                start_price,
+                mode: mode_value,
+                start_ledger,
+            },
+        );
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("round"), symbol_short!("scheduled")),
+            (start_price, start_ledger, mode_value),
+        );
+
+        Ok(())
+    }
+
+    /// Opens the scheduled round once its start ledger is reached (callable by anyone)
+    /// The round starts at the current ledger using the configured windows
+    pub fn activate_scheduled_round(env: Env) -> Result<(), ContractError> {
+        let scheduled: ScheduledRound = env
             .storage()
             .persistent()
-            .get(&DataKey::ActiveRound)
-            .ok_or(ContractError::NoActiveRound)?;
+            .get(&DataKey::ScheduledRound)
+            .ok_or(ContractError::NoScheduledRound)?;
 
-        // Verify round is in Up/Down mode
-        if round.mode != RoundMode::UpDown {
-            return Err(ContractError::WrongModeForPrediction);
+        if env.ledger().sequence() < scheduled.start_ledger {
+            return Err(ContractError::TooEarlyToActivate);
         }
 
-        let current_ledger = env.ledger().sequence();
-        if current_ledger >= round.bet_end_ledger {
-            return Err(ContractError::RoundEnded);
-        }
+        Self::_open_round(&env, scheduled.start_price, scheduled.mode)?;
+        env.storage().persistent().remove(&DataKey::ScheduledRound);
 
-        let user_balance = Self::balance(env.clone(), user.clone());
-        if user_balance < amount {
-            return Err(ContractError::InsufficientBalance);
-        }
+        Ok(())
+    }
 
-        // Use UpDownPositions storage for Up/Down mode
-        let mut positions: Map<Address, UserPosition> = env
+    /// Removes the pending scheduled round before it is activated (admin only)
+    pub fn cancel_scheduled_round(env: Env) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        let scheduled: ScheduledRound = env
             .storage()
             .persistent()
-            .get(&DataKey::UpDownPositions)
-            .unwrap_or(Map::new(&env));
+            .get(&DataKey::ScheduledRound)
+            .ok_or(ContractError::NoScheduledRound)?;
 
-        if positions.contains_key(user.clone()) {
-            return Err(ContractError::AlreadyBet);
-        }
+        env.storage().persistent().remove(&DataKey::ScheduledRound);
 
-        let new_balance = user_balance
-            .checked_sub(amount)
-            .ok_or(ContractError::Overflow)?;
-        Self::_set_balance(&env, user.clone(), new_balance);
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("round"), symbol_short!("unsched")),
+            (scheduled.start_price, scheduled.start_ledger),
+        );
 
-        let position = UserPosition {
-            amount,
-            side: side.clone(),
-        };
-        positions.set(user.clone(), position);
+        Ok(())
+    }
 
-        match side {
-            BetSide::Up => {
-                round.pool_up = round
-                    .pool_up
-                    .checked_add(amount)
-                    .ok_or(ContractError::Overflow)?;
-            }
-            BetSide::Down => {
-                round.pool_down = round
-                    .pool_down
-                    .checked_add(amount)
-                    .ok_or(ContractError::Overflow)?;
-            }
-        }
+    /// Returns the round waiting to be activated, if any
+    pub fn get_scheduled_round(env: Env) -> Option<ScheduledRound> {
+        env.storage().persistent().get(&DataKey::ScheduledRound)
+    }
 
-        env.storage()
+    /// Stops betting on the active round at the current ledger (admin only)
+    /// end_ledger is left unchanged, so the round resolves on schedule
+    pub fn close_betting(env: Env) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        let mut round: Round = env
+            .storage()
             .persistent()
-            .set(&DataKey::UpDownPositions, &positions);
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        // Never reopen a window that already closed
+        round.bet_end_ledger = round.bet_end_ledger.min(env.ledger().sequence());
+        round.bet_end_timestamp = round.bet_end_timestamp.min(env.ledger().timestamp());
         env.storage()
             .persistent()
             .set(&DataKey::ActiveRound, &round);
 
-        // Also keep legacy Positions storage for backwards compatibility
-        let mut legacy_positions: Map<Address, UserPosition> = env
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("round"), symbol_short!("closed")),
+            (round.start_ledger, round.bet_end_ledger),
+        );
+
+        Ok(())
+    }
+
+    /// Reopens betting on the active round until new_bet_end_ledger (admin only)
+    /// Undoes an early close_betting; the new close must be in the future and before end_ledger
+    pub fn reopen_betting(env: Env, new_bet_end_ledger: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        let mut round: Round = env
             .storage()
             .persistent()
-            .get(&DataKey::Positions)
-            .unwrap_or(Map::new(&env));
-        legacy_positions.set(user, UserPosition { amount, side });
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        if new_bet_end_ledger <= env.ledger().sequence() || new_bet_end_ledger >= round.end_ledger {
+            return Err(ContractError::InvalidDuration);
+        }
+
+        round.bet_end_ledger = new_bet_end_ledger;
+        round.bet_end_timestamp = Self::_ledger_timestamp(
+            round.created_timestamp,
+            new_bet_end_ledger - round.start_ledger,
+        );
         env.storage()
             .persistent()
-            .set(&DataKey::Positions, &legacy_positions);
+            .set(&DataKey::ActiveRound, &round);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("round"), symbol_short!("reopened")),
+            (round.start_ledger, round.bet_end_ledger),
+        );
 
         Ok(())
     }
 
-    /// Places a precision prediction on the active round (Precision/Legends mode only)
-    /// predicted_price: price scaled to 4 decimals (e.g., 0.2297 → 2297)
-    pub fn place_precision_prediction(
-        env: Env,
-        user: Address,
-        amount: i128,
-        predicted_price: u128,
-    ) -> Result<(), ContractError> {
-        user.require_auth();
-
-        if amount <= 0 {
-            return Err(ContractError::InvalidBetAmount);
-        }
-
-        // Validate price scale (must be 4 decimal places, max value 9999 for 0.9999)
-        // Reasonable max: 99999999 (9999.9999 XLM)
-        if predicted_price > 99_999_999 {
-            return Err(ContractError::InvalidPriceScale);
-        }
+    /// Pushes the active round's end_ledger back by extra_ledgers (admin only)
+    /// If betting is still open its close moves by the same amount; the whole round
+    /// may not run longer than MAX_RUN_LEDGERS
+    pub fn extend_round(env: Env, extra_ledgers: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
 
-        let round: Round = env
+        let mut round: Round = env
             .storage()
             .persistent()
             .get(&DataKey::ActiveRound)
             .ok_or(ContractError::NoActiveRound)?;
 
-        // Verify round is in Precision mode
-        if round.mode != RoundMode::Precision {
-            return Err(ContractError::WrongModeForPrediction);
+        if extra_ledgers == 0 {
+            return Err(ContractError::InvalidDuration);
         }
 
-        let current_ledger = env.ledger().sequence();
-        if current_ledger >= round.bet_end_ledger {
-            return Err(ContractError::RoundEnded);
+        let end_ledger = round
+            .end_ledger
+            .checked_add(extra_ledgers)
+            .ok_or(ContractError::Overflow)?;
+        let run_ledgers = end_ledger - round.start_ledger;
+        if run_ledgers > MAX_RUN_LEDGERS {
+            return Err(ContractError::InvalidDuration);
         }
 
-        let user_balance = Self::balance(env.clone(), user.clone());
-        if user_balance < amount {
-            return Err(ContractError::InsufficientBalance);
+        round.end_ledger = end_ledger;
+        round.end_timestamp = Self::_ledger_timestamp(round.created_timestamp, run_ledgers);
+
+        if env.ledger().sequence() < round.bet_end_ledger {
+            round.bet_end_ledger = round
+                .bet_end_ledger
+                .checked_add(extra_ledgers)
+                .ok_or(ContractError::Overflow)?;
+            round.bet_end_timestamp = Self::_ledger_timestamp(
+                round.created_timestamp,
+                round.bet_end_ledger - round.start_ledger,
+            );
         }
 
-        // Check if user already has a prediction in this round
-        let mut predictions: Map<Address, PrecisionPrediction> = env
+        env.storage()
+            .persistent()
+            .set(&DataKey::ActiveRound, &round);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("round"), symbol_short!("extended")),
+            (round.start_ledger, round.bet_end_ledger, round.end_ledger),
+        );
+
+        Ok(())
+    }
+
+    /// Previews (start, bet_end, end) ledgers of a round starting at start_ledger
+    /// Uses the configured windows; nothing is stored
+    pub fn preview_windows(env: Env, start_ledger: u32) -> (u32, u32, u32) {
+        let (bet_ledgers, run_ledgers) = Self::_window_ledgers(&env);
+
+        (
+            start_ledger,
+            start_ledger.saturating_add(bet_ledgers),
+            start_ledger.saturating_add(run_ledgers),
+        )
+    }
+
+    /// Returns the configured (bet, run) window lengths in ledgers, defaulting to (6, 12)
+    fn _window_ledgers(env: &Env) -> (u32, u32) {
+        let bet_ledgers: u32 = env
             .storage()
             .persistent()
-            .get(&DataKey::PrecisionPositions)
-            .unwrap_or(Map::new(&env));
+            .get(&DataKey::BetWindowLedgers)
+            .unwrap_or(DEFAULT_BET_LEDGERS);
+        let run_ledgers: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RunWindowLedgers)
+            .unwrap_or(DEFAULT_RUN_LEDGERS);
 
-        if predictions.contains_key(user.clone()) {
-            return Err(ContractError::AlreadyBet);
-        }
+        (bet_ledgers, run_ledgers)
+    }
 
-        // Deduct balance
-        let new_balance = user_balance
-            .checked_sub(amount)
-            .ok_or(ContractError::Overflow)?;
-        Self::_set_balance(&env, user.clone(), new_balance);
+    /// Estimated timestamp `ledgers` ledgers after `from`; advisory only, ledgers are authoritative
+    fn _ledger_timestamp(from: u64, ledgers: u32) -> u64 {
+        from.saturating_add((ledgers as u64).saturating_mul(SECONDS_PER_LEDGER))
+    }
 
-        // Store prediction
-        let prediction = PrecisionPrediction {
-            user: user.clone(),
-            predicted_price,
-            amount,
+    /// Moves positions stored under the legacy `Positions` key to `UpDownPositions` (admin only)
+    /// Entries already present under the new key win; returns how many were moved.
+    /// v1 entries carry no round id and are assigned the active round's (0 without one)
+    pub fn migrate_v1_to_v2(env: Env) -> Result<u32, ContractError> {
+        Self::_require_admin(&env)?;
+
+        let legacy: Map<Address, Val> = match env.storage().persistent().get(&DataKey::Positions) {
+            Some(legacy) => legacy,
+            None => return Ok(0),
         };
-        predictions.set(user.clone(), prediction);
+
+        // v1 ran one round at a time; its Round schema differs too, so only the id is read
+        let round_id = env
+            .storage()
+            .persistent()
+            .get::<_, Map<Symbol, Val>>(&DataKey::ActiveRound)
+            .and_then(|round| round.get(Symbol::new(&env, "start_ledger")))
+            .and_then(|start_ledger| u32::try_from_val(&env, &start_ledger).ok())
+            .unwrap_or(0);
+
+        let mut positions = Self::get_updown_positions(env.clone());
+        let mut migrated: u32 = 0;
+        for (user, raw) in legacy.iter() {
+            if !positions.contains_key(user.clone()) {
+                positions.set(user, Self::_decode_legacy_position(&env, raw, round_id)?);
+                migrated += 1;
+            }
+        }
 
         env.storage()
             .persistent()
-            .set(&DataKey::PrecisionPositions, &predictions);
+            .set(&DataKey::UpDownPositions, &positions);
+        env.storage().persistent().remove(&DataKey::Positions);
 
-        // Emit event for precision prediction
         #[allow(deprecated)]
         env.events().publish(
-            (symbol_short!("predict"), symbol_short!("price")),
-            (user, predicted_price, round.start_ledger),
+            (symbol_short!("admin"), symbol_short!("migrated")),
+            migrated,
         );
 
-        Ok(())
+        Ok(migrated)
     }
 
-    /// Alias for place_precision_prediction - allows users to submit exact price predictions
-    /// guessed_price: price scaled to 4 decimals (e.g., 0.2297 → 2297)
-    pub fn predict_price(
-        env: Env,
-        user: Address,
-        guessed_price: u128,
-        amount: i128,
-    ) -> Result<(), ContractError> {
-        Self::place_precision_prediction(env, user, amount, guessed_price)
-    }
+    /// Refunds and clears Up/Down positions left behind by a round that is no longer active (admin only)
+    /// Bets are mirrored into the legacy `Positions` map, so each purged user is dropped
+    /// from it too but refunded once; returns how many positions were purged
+    pub fn purge_orphaned_positions(env: Env, round_id: u32) -> Result<u32, ContractError> {
+        Self::_require_admin(&env)?;
 
-    /// Returns user's position in the current round (Up/Down mode)
-    pub fn get_user_position(env: Env, user: Address) -> Option<UserPosition> {
-        let positions: Map<Address, UserPosition> = env
+        let active: Option<Round> = env.storage().persistent().get(&DataKey::ActiveRound);
+        if active.is_some_and(|round| round.start_ledger == round_id) {
+            return Err(ContractError::RoundAlreadyActive);
+        }
+
+        let mut positions = Self::get_updown_positions(env.clone());
+        // Legacy entries may predate `round_id`, so they are only removed, never decoded
+        let mut legacy: Map<Address, Val> = env
             .storage()
             .persistent()
-            .get(&DataKey::UpDownPositions)
+            .get(&DataKey::Positions)
             .unwrap_or(Map::new(&env));
 
-        positions.get(user)
+        let mut purged: u32 = 0;
+        for (user, position) in positions.clone().iter() {
+            if position.round_id != round_id {
+                continue;
+            }
+            let refund = Payout {
+                user: user.clone(),
+                amount: position.amount,
+                kind: PayoutKind::Refund,
+            };
+            Self::_apply_payout(&env, &refund, round_id)?;
+            positions.remove(user.clone());
+            legacy.remove(user);
+            purged += 1;
+        }
+
+        if positions.is_empty() {
+            env.storage().persistent().remove(&DataKey::UpDownPositions);
+        } else {
+            env.storage()
+                .persistent()
+                .set(&DataKey::UpDownPositions, &positions);
+        }
+        if legacy.is_empty() {
+            env.storage().persistent().remove(&DataKey::Positions);
+        } else {
+            env.storage().persistent().set(&DataKey::Positions, &legacy);
+        }
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("admin"), symbol_short!("purged")),
+            (round_id, purged),
+        );
+
+        Ok(purged)
     }
 
-    /// Returns user's precision prediction in the current round (Precision mode)
-    pub fn get_user_precision_prediction(env: Env, user: Address) -> Option<PrecisionPrediction> {
-        let predictions: Map<Address, PrecisionPrediction> = env
+    /// Returns the currently active round, if any
+    /// While the result retention window is open this is the just-resolved
+    /// round, with `resolved` set and its `final_price`; get_round_status tells the two apart
+    pub fn get_active_round(env: Env) -> Option<Round> {
+        if let Some(round) = env.storage().persistent().get(&DataKey::ActiveRound) {
+            return Some(round);
+        }
+
+        let round: Round = env.storage().persistent().get(&DataKey::ResolvedRound)?;
+        let resolved_at: u32 = env
             .storage()
             .persistent()
-            .get(&DataKey::PrecisionPositions)
-            .unwrap_or(Map::new(&env));
-
-        predictions.get(user)
+            .get(&DataKey::LastResolvedLedger)?;
+        let retention = Self::get_result_retention(env.clone());
+        if env.ledger().sequence() > resolved_at.saturating_add(retention) {
+            return None;
+        }
+        Some(round)
     }
 
-    /// Returns all precision predictions for the current round
-    pub fn get_precision_predictions(env: Env) -> Vec<PrecisionPrediction> {
-        let predictions: Map<Address, PrecisionPrediction> = env
+    /// Whether get_active_round would return a round taking bets, one awaiting resolution
+    /// or a resolved round kept for the results reveal
+    pub fn get_round_status(env: Env) -> RoundStatus {
+        if let Some(round) = env
             .storage()
             .persistent()
-            .get(&DataKey::PrecisionPositions)
-            .unwrap_or(Map::new(&env));
-        predictions.values()
+            .get::<_, Round>(&DataKey::ActiveRound)
+        {
+            if env.ledger().sequence() < round.bet_end_ledger {
+                return RoundStatus::Open;
+            }
+            return RoundStatus::Closed;
+        }
+
+        match Self::get_active_round(env) {
+            Some(_) => RoundStatus::Resolved,
+            None => RoundStatus::None,
+        }
     }
 
-    /// Returns all Up/Down positions for the current round
-    pub fn get_updown_positions(env: Env) -> Map<Address, UserPosition> {
+    /// Removes a resolved round kept for the results reveal (admin only)
+    pub fn clear_resolved(env: Env) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        let storage = env.storage().persistent();
+        let round: Round = match storage.get(&DataKey::ResolvedRound) {
+            Some(round) => round,
+            None => return Ok(()),
+        };
+        storage.remove(&DataKey::ResolvedRound);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("round"), symbol_short!("cleared")),
+            round.start_ledger,
+        );
+
+        Ok(())
+    }
+
+    /// Returns the currently active round, or NoActiveRound if there is none
+    pub fn get_active_round_checked(env: Env) -> Result<Round, ContractError> {
         env.storage()
             .persistent()
-            .get(&DataKey::UpDownPositions)
-            .unwrap_or(Map::new(&env))
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)
     }
 
-    /// Resolves the round with oracle payload (oracle only)
-    /// Mode 0 (Up/Down): Winners split losers' pool proportionally; ties get refunds
-    /// Mode 1 (Precision/Legends): Closest guess wins full pot; ties split evenly
-    pub fn resolve_round(env: Env, payload: OraclePayload) -> Result<(), ContractError> {
-        if payload.price == 0 {
-            return Err(ContractError::InvalidPrice);
+    /// Returns the active round, or StateDecodeError if the stored round predates the current schema
+    /// Unlike get_active_round this never traps on undecodable state; Ok(None) if there is no round
+    pub fn get_active_round_safe(env: Env) -> Result<Option<Round>, ContractError> {
+        let raw: Val = match env.storage().persistent().get(&DataKey::ActiveRound) {
+            Some(raw) => raw,
+            None => return Ok(None),
+        };
+
+        // The host traps when a stored struct's keys differ from the expected ones,
+        // so compare the field names before decoding
+        let fields = Map::<Val, Val>::try_from_val(&env, &raw)
+            .map_err(|_| ContractError::StateDecodeError)?;
+        let matches_schema = fields.len() == ROUND_FIELDS.len() as u32
+            && ROUND_FIELDS
+                .iter()
+                .all(|name| fields.contains_key(Symbol::new(&env, name).to_val()));
+        if !matches_schema {
+            return Err(ContractError::StateDecodeError);
         }
 
-        let oracle: Address = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Oracle)
-            .ok_or(ContractError::OracleNotSet)?;
+        Round::try_from_val(&env, &raw)
+            .map(Some)
+            .map_err(|_| ContractError::StateDecodeError)
+    }
 
-        oracle.require_auth();
+    /// Returns ledgers remaining until the active round can be resolved
+    /// 0 if already resolvable, None if there is no active round
+    pub fn ledgers_until_resolvable(env: Env) -> Option<u32> {
+        let round: Round = env.storage().persistent().get(&DataKey::ActiveRound)?;
+        Some(round.end_ledger.saturating_sub(env.ledger().sequence()))
+    }
 
-        let round: Round = env
-            .storage()
-            .persistent()
-            .get(&DataKey::ActiveRound)
-            .ok_or(ContractError::NoActiveRound)?;
+    /// Returns true once `initialize` has set an admin
+    pub fn is_initialized(env: Env) -> bool {
+        env.storage().persistent().has(&DataKey::Admin)
+    }
 
-        // Verify round ID matches to prevent cross-round replays
-        if payload.round_id != round.start_ledger {
-            return Err(ContractError::InvalidOracleRound);
-        }
+    /// Returns true if an oracle address is configured
+    pub fn is_oracle_set(env: Env) -> bool {
+        env.storage().persistent().has(&DataKey::Oracle)
+    }
 
-        // Verify data freshness (max 300 seconds / 5 minutes old)
-        let current_time = env.ledger().timestamp();
-        if current_time > payload.timestamp + 300 {
-            return Err(ContractError::StaleOracleData);
-        }
+    pub fn get_admin(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Admin)
+    }
 
-        // Verify round has reached end_ledger
-        let current_ledger = env.ledger().sequence();
-        if current_ledger < round.end_ledger {
-            return Err(ContractError::RoundNotEnded);
-        }
+    pub fn get_oracle(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Oracle)
+    }
 
-        // Branch based on round mode
-        match round.mode {
-            RoundMode::UpDown => {
-                Self::_resolve_updown_mode(&env, &round, payload.price)?;
-            }
-            RoundMode::Precision => {
-                Self::_resolve_precision_mode(&env, payload.price)?;
-            }
+    /// Rotates the oracle address (admin only)
+    pub fn set_oracle(env: Env, new_oracle: Address) -> Result<(), ContractError> {
+        let admin = Self::_require_admin(&env)?;
+
+        if new_oracle == admin {
+            return Err(ContractError::AdminOracleConflict);
         }
 
-        // Clean up storage
-        env.storage().persistent().remove(&DataKey::ActiveRound);
-        env.storage().persistent().remove(&DataKey::Positions);
-        env.storage().persistent().remove(&DataKey::UpDownPositions);
         env.storage()
             .persistent()
-            .remove(&DataKey::PrecisionPositions);
+            .set(&DataKey::Oracle, &new_oracle);
 
-        // Emit resolution event
         #[allow(deprecated)]
         env.events().publish(
-            (symbol_short!("round"), symbol_short!("resolved")),
-            payload.price,
+            (symbol_short!("oracle"), symbol_short!("updated")),
+            new_oracle,
         );
 
         Ok(())
     }
 
-    /// Resolves Up/Down mode round
-    fn _resolve_updown_mode(
-        env: &Env,
-        round: &Round,
-        final_price: u128,
-    ) -> Result<(), ContractError> {
-        let positions: Map<Address, UserPosition> = env
+    /// Transfers the admin role to a new address (admin only)
+    pub fn transfer_admin(env: Env, new_admin: Address) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        let oracle: Address = env
             .storage()
             .persistent()
-            .get(&DataKey::UpDownPositions)
-            .unwrap_or(Map::new(env));
+            .get(&DataKey::Oracle)
+            .ok_or(ContractError::OracleNotSet)?;
+        if new_admin == oracle {
+            return Err(ContractError::AdminOracleConflict);
+        }
 
-        let price_went_up = final_price > round.price_start;
-        let price_went_down = final_price < round.price_start;
-        let price_unchanged = final_price == round.price_start;
+        env.storage().persistent().set(&DataKey::Admin, &new_admin);
 
-        if price_unchanged {
-            Self::_record_refunds(env, positions)?;
-        } else if price_went_up {
-            Self::_record_winnings(env, positions, BetSide::Up, round.pool_up, round.pool_down)?;
-        } else if price_went_down {
-            Self::_record_winnings(
-                env,
-                positions,
-                BetSide::Down,
-                round.pool_down,
-                round.pool_up,
-            )?;
-        }
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("admin"), symbol_short!("updated")),
+            new_admin,
+        );
 
         Ok(())
     }
 
-    /// Resolves Precision/Legends mode round
-    /// Awards full pot to closest guess(es); ties split evenly
-    fn _resolve_precision_mode(env: &Env, final_price: u128) -> Result<(), ContractError> {
-        let predictions_map: Map<Address, PrecisionPrediction> = env
+    /// Sets the betting and execution windows (admin only)
+    /// bet_ledgers: Number of ledgers users can place bets
+    /// run_ledgers: Total number of ledgers before round can be resolved (at most 100_000)
+    pub fn set_windows(env: Env, bet_ledgers: u32, run_ledgers: u32) -> Result<(), ContractError> {
+        let admin: Address = env
             .storage()
             .persistent()
-            .get(&DataKey::PrecisionPositions)
-            .unwrap_or(Map::new(env));
-        let predictions = predictions_map.values();
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
 
-        // If no predictions, nothing to resolve
-        if predictions.is_empty() {
-            return Ok(());
-        }
+        admin.require_auth();
 
-        // Find minimum difference and collect all winners
-        let mut min_diff: Option<u128> = None;
-        let mut winners: Vec<PrecisionPrediction> = Vec::new(env);
+        // Validate both values are positive
+        if bet_ledgers == 0 || run_ledgers == 0 {
+            return Err(ContractError::InvalidDuration);
+        }
 
-        for i in 0..predictions.len() {
-            if let Some(pred) = predictions.get(i) {
-                // Calculate absolute difference using checked arithmetic
-                let diff = if pred.predicted_price >= final_price {
-                    pred.predicted_price
-                        .checked_sub(final_price)
-                        .ok_or(ContractError::Overflow)?
+        // Validate bet window closes before run window ends
+        if bet_ledgers >= run_ledgers {
+            return Err(ContractError::InvalidDuration);
+        }
+
+        if run_ledgers > MAX_RUN_LEDGERS {
+            return Err(ContractError::InvalidDuration);
+        }
+
+        // Leave users a usable amount of time to bet (~5s per ledger)
+        if bet_ledgers < Self::get_min_bet_ledgers(env.clone()) {
+            return Err(ContractError::InvalidDuration);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::BetWindowLedgers, &bet_ledgers);
+        env.storage()
+            .persistent()
+            .set(&DataKey::RunWindowLedgers, &run_ledgers);
+
+        // Emit event
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("windows"), symbol_short!("updated")),
+            (bet_ledgers, run_ledgers),
+        );
+
+        Ok(())
+    }
+
+    /// Sets the mode create_round uses when none is passed (admin only)
+    /// mode: 0 = Up/Down, 1 = Precision
+    pub fn set_default_mode(env: Env, mode: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if mode > 1 {
+            return Err(ContractError::InvalidMode);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::DefaultMode, &mode);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("def_mode")), mode);
+
+        Ok(())
+    }
+
+    /// Returns the mode used when create_round is called without one (0 = Up/Down)
+    pub fn get_default_mode(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::DefaultMode)
+            .unwrap_or(0)
+    }
+
+    /// Sets the shortest bet window set_windows will accept (admin only)
+    pub fn set_min_bet_ledgers(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if ledgers == 0 {
+            return Err(ContractError::InvalidDuration);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::MinBetLedgers, &ledgers);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("min_bet")), ledgers);
+
+        Ok(())
+    }
+
+    /// Returns the shortest allowed bet window in ledgers (default 2)
+    pub fn get_min_bet_ledgers(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::MinBetLedgers)
+            .unwrap_or(DEFAULT_MIN_BET_LEDGERS)
+    }
+
+    /// Sets the draw band around the start price, in basis points (admin only)
+    /// Up/Down rounds whose final price moves no more than this are refunded
+    pub fn set_draw_band_bps(env: Env, bps: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if bps > 10_000 {
+            return Err(ContractError::InvalidBasisPoints);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::DrawBandBps, &bps);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("draw_band")), bps);
+
+        Ok(())
+    }
+
+    /// Returns the draw band in basis points (0 = only an exact match refunds)
+    pub fn get_draw_band_bps(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::DrawBandBps)
+            .unwrap_or(0)
+    }
+
+    /// Sets the maximum total pot per round (admin only)
+    /// cap: 0 = unlimited
+    pub fn set_max_pot(env: Env, cap: i128) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if cap < 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
+
+        env.storage().persistent().set(&ConfigKey::MaxPot, &cap);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("max_pot")), cap);
+
+        Ok(())
+    }
+
+    /// Returns the maximum total pot per round (0 = unlimited)
+    pub fn get_max_pot(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::MaxPot)
+            .unwrap_or(0)
+    }
+
+    /// Caps how much a user can stake per period of ledgers (admin only)
+    /// amount: 0 disables the limit
+    pub fn set_daily_limit(
+        env: Env,
+        amount: i128,
+        period_ledgers: u32,
+    ) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if amount < 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
+        if amount > 0 && period_ledgers == 0 {
+            return Err(ContractError::InvalidDuration);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::DailyLimit, &(amount, period_ledgers));
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("day_limit")),
+            (amount, period_ledgers),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the staking limit as (amount, period_ledgers); amount 0 = no limit
+    pub fn get_daily_limit(env: Env) -> (i128, u32) {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::DailyLimit)
+            .unwrap_or((0, 0))
+    }
+
+    /// Sets the minimum number of ledgers between a resolution and the next round (admin only)
+    pub fn set_round_cooldown(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::RoundCooldown, &ledgers);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("cooldown")),
+            ledgers,
+        );
+
+        Ok(())
+    }
+
+    /// Returns the cooldown between rounds in ledgers (0 = none)
+    pub fn get_round_cooldown(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::RoundCooldown)
+            .unwrap_or(0)
+    }
+
+    /// Sets how many ledgers a resolved round stays readable via get_active_round (admin only)
+    /// 0 clears the round as soon as it resolves
+    pub fn set_result_retention(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::ResultRetention, &ledgers);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("retention")),
+            ledgers,
+        );
+
+        Ok(())
+    }
+
+    /// Returns how many ledgers a resolved round stays readable (0 = none)
+    pub fn get_result_retention(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::ResultRetention)
+            .unwrap_or(0)
+    }
+
+    /// Sets how many of the most recent round results are kept (admin only)
+    /// Older results are pruned as rounds resolve, at most MAX_HISTORY_PRUNE per resolution
+    pub fn set_history_limit(env: Env, n: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if n == 0 {
+            return Err(ContractError::InvalidHistoryLimit);
+        }
+
+        env.storage().persistent().set(&ConfigKey::HistoryLimit, &n);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("history")), n);
+
+        Ok(())
+    }
+
+    /// Returns how many of the most recent round results are kept (default 1000)
+    pub fn get_history_limit(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::HistoryLimit)
+            .unwrap_or(DEFAULT_HISTORY_LIMIT)
+    }
+
+    /// Blocks or unblocks an address from minting and betting (admin only)
+    /// Winnings the user has already earned stay claimable
+    pub fn set_blacklisted(env: Env, user: Address, blocked: bool) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        let key = DataKey::Blacklist(user.clone());
+        if blocked {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("blacklist"), symbol_short!("updated")),
+            (user, blocked),
+        );
+
+        Ok(())
+    }
+
+    /// Returns true if the address is blocked from minting and betting
+    pub fn is_blacklisted(env: Env, user: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Blacklist(user))
+    }
+
+    /// Allows or forbids the oracle address from betting (admin only)
+    /// Forbidden by default: the oracle settles the rounds it would be betting on
+    pub fn set_oracle_can_bet(env: Env, enabled: bool) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::OracleCanBet, &enabled);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("orcl_bet")),
+            enabled,
+        );
+
+        Ok(())
+    }
+
+    /// Returns true if the oracle address may bet
+    pub fn get_oracle_can_bet(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::OracleCanBet)
+            .unwrap_or(false)
+    }
+
+    /// Sets how many participants a round needs before it pays out winners (admin only)
+    /// Rounds with fewer participants refund every stake; 0 or 1 = always pay out
+    pub fn set_min_participants_to_pay(env: Env, n: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::MinParticipants, &n);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("min_part")), n);
+
+        Ok(())
+    }
+
+    /// Sets the share of their own stake Up/Down losers get back, in basis points (admin only)
+    /// Paid out of the losing pool before winners split the rest
+    pub fn set_consolation_bps(env: Env, bps: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if bps > 10_000 {
+            return Err(ContractError::InvalidBasisPoints);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::ConsolationBps, &bps);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("consol")), bps);
+
+        Ok(())
+    }
+
+    /// Caps the share of a round's pot a single user's stake may make up (admin only)
+    /// bps is measured against the pot including the new bet; 0 disables the check
+    pub fn set_max_user_share_bps(env: Env, bps: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if bps > 10_000 {
+            return Err(ContractError::InvalidBasisPoints);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::MaxUserShareBps, &bps);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("max_share")), bps);
+
+        Ok(())
+    }
+
+    /// Returns the largest share of a pot one user may hold, in basis points (0 = unlimited)
+    pub fn get_max_user_share_bps(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::MaxUserShareBps)
+            .unwrap_or(0)
+    }
+
+    /// Selects how Precision rounds pay out (admin only)
+    /// mode: 0 = closest guess(es) take the pot, 1 = shares weighted by stake / (1 + distance),
+    /// 2 = closest guess takes the pot, ties broken by a stake-weighted draw,
+    /// 3 = guesses in the final price's zone share the pot by stake (needs zones set first)
+    pub fn set_precision_payout_mode(env: Env, mode: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if mode != PRECISION_PAYOUT_CLOSEST
+            && mode != PRECISION_PAYOUT_INVERSE_DISTANCE
+            && mode != PRECISION_PAYOUT_CLOSEST_DRAW
+            && mode != PRECISION_PAYOUT_ZONE
+        {
+            return Err(ContractError::InvalidMode);
+        }
+        if mode == PRECISION_PAYOUT_ZONE && Self::get_precision_zones(env.clone()).is_empty() {
+            return Err(ContractError::ZoneNotConfigured);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::PrecisionPayoutMode, &mode);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("prec_pay")), mode);
+
+        Ok(())
+    }
+
+    /// Returns the Precision payout mode (0 = closest wins, 1 = inverse distance,
+    /// 2 = closest with draw, 3 = price zone)
+    pub fn get_precision_payout_mode(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::PrecisionPayoutMode)
+            .unwrap_or(PRECISION_PAYOUT_CLOSEST)
+    }
+
+    /// Sets the boundaries splitting prices into zones for zone payouts (admin only)
+    /// n ascending boundaries make n + 1 zones; a price equal to a boundary falls in the upper zone.
+    /// Can't change while a Precision round is open; an empty list clears the zones
+    pub fn set_precision_zones(env: Env, boundaries: Vec<u128>) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        let precision_round_open = env
+            .storage()
+            .persistent()
+            .get::<_, Round>(&DataKey::ActiveRound)
+            .is_some_and(|round| round.mode == RoundMode::Precision);
+        if precision_round_open {
+            return Err(ContractError::RoundAlreadyActive);
+        }
+
+        if boundaries.is_empty() {
+            if Self::get_precision_payout_mode(env.clone()) == PRECISION_PAYOUT_ZONE {
+                return Err(ContractError::ZoneNotConfigured);
+            }
+            env.storage()
+                .persistent()
+                .remove(&ConfigKey::PrecisionZones);
+        } else {
+            if boundaries.len() > MAX_PRECISION_ZONES {
+                return Err(ContractError::InvalidPrice);
+            }
+            let mut previous = 0u128;
+            for boundary in boundaries.iter() {
+                if boundary <= previous {
+                    return Err(ContractError::InvalidPrice);
+                }
+                previous = boundary;
+            }
+
+            env.storage()
+                .persistent()
+                .set(&ConfigKey::PrecisionZones, &boundaries);
+        }
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("zones")),
+            boundaries,
+        );
+
+        Ok(())
+    }
+
+    /// Returns the Precision zone boundaries, ascending (empty if none are set)
+    pub fn get_precision_zones(env: Env) -> Vec<u128> {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::PrecisionZones)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Sets the most a single Precision winner may receive (admin only)
+    /// cap: 0 removes the cap. The excess goes where get_payout_cap_policy says
+    pub fn set_precision_max_payout(env: Env, cap: i128) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if cap < 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::PrecisionMaxPayout, &cap);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("max_win")), cap);
+
+        Ok(())
+    }
+
+    /// Returns the most a single Precision winner may receive (0 = no cap)
+    pub fn get_precision_max_payout(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::PrecisionMaxPayout)
+            .unwrap_or(0)
+    }
+
+    /// Chooses where the excess of a capped Precision payout goes (admin only)
+    /// policy: 0 = fee treasury, 1 = rollover pot for a later round's winners
+    pub fn set_payout_cap_policy(env: Env, policy: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if policy != PAYOUT_CAP_TREASURY && policy != PAYOUT_CAP_ROLLOVER {
+            return Err(ContractError::InvalidMode);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::PayoutCapPolicy, &policy);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("cap_pol")), policy);
+
+        Ok(())
+    }
+
+    /// Returns where capped payout excess goes (0 = fee treasury)
+    pub fn get_payout_cap_policy(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::PayoutCapPolicy)
+            .unwrap_or(PAYOUT_CAP_TREASURY)
+    }
+
+    /// Selects how Precision guesses are measured against the final price (admin only)
+    /// mode: 0 = absolute stroop distance, 1 = basis points of the final price.
+    /// The precision tolerance is read in the same unit
+    pub fn set_precision_distance_mode(env: Env, mode: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if mode != PRECISION_DISTANCE_ABSOLUTE && mode != PRECISION_DISTANCE_PERCENT {
+            return Err(ContractError::InvalidMode);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::PrecisionDistanceMode, &mode);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("prec_dist")), mode);
+
+        Ok(())
+    }
+
+    /// Returns the Precision distance mode (0 = absolute, 1 = percent of final price)
+    pub fn get_precision_distance_mode(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::PrecisionDistanceMode)
+            .unwrap_or(PRECISION_DISTANCE_ABSOLUTE)
+    }
+
+    /// Returns the losers' consolation in basis points (0 = none)
+    pub fn get_consolation_bps(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::ConsolationBps)
+            .unwrap_or(0)
+    }
+
+    /// Selects how Up/Down winners' shares are rounded (admin only)
+    /// mode: 0 = floor, dust stays with the protocol; 1 = nearest stroop, never paying
+    /// out more than the losing pool
+    pub fn set_rounding(env: Env, mode: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if mode != ROUNDING_FLOOR && mode != ROUNDING_NEAREST {
+            return Err(ContractError::InvalidMode);
+        }
+
+        env.storage().persistent().set(&ConfigKey::Rounding, &mode);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("rounding")), mode);
+
+        Ok(())
+    }
+
+    /// Returns the share rounding mode (0 = floor, 1 = nearest)
+    pub fn get_rounding(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::Rounding)
+            .unwrap_or(ROUNDING_FLOOR)
+    }
+
+    /// Sets the house fee taken from the Up/Down losing pool before winners split it (admin only)
+    pub fn set_fee_bps(env: Env, bps: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if bps > 10_000 {
+            return Err(ContractError::InvalidBasisPoints);
+        }
+
+        env.storage().persistent().set(&ConfigKey::FeeBps, &bps);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("fee")), bps);
+
+        Ok(())
+    }
+
+    /// Returns the house fee in basis points (0 = none)
+    pub fn get_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::FeeBps)
+            .unwrap_or(0)
+    }
+
+    /// Chooses how Up/Down rounds with stakes on only one side resolve (admin only)
+    /// policy: 0 = settle as usual (stakes come back), 1 = refund minus the house fee,
+    /// 2 = carry the whole pot over to the winners of a later round
+    pub fn set_one_sided_policy(env: Env, policy: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if policy != ONE_SIDED_REFUND
+            && policy != ONE_SIDED_VOID_WITH_FEE
+            && policy != ONE_SIDED_ROLLOVER
+        {
+            return Err(ContractError::InvalidMode);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::OneSidedPolicy, &policy);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("one_sided")),
+            policy,
+        );
+
+        Ok(())
+    }
+
+    /// Returns the one-sided round policy (0 = settle as usual)
+    pub fn get_one_sided_policy(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::OneSidedPolicy)
+            .unwrap_or(ONE_SIDED_REFUND)
+    }
+
+    /// Returns vXLM carried over from one-sided rounds, paid to the next round with winners
+    pub fn get_rollover_pot(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RolloverPot)
+            .unwrap_or(0)
+    }
+
+    /// Sets how far from the final price a Precision guess may be and still win (admin only)
+    /// None = exact guesses only in the closest-guess modes, Some(u128::MAX) = the closest
+    /// guess always wins. If no guess is within tolerance every prediction is refunded
+    pub fn set_precision_tolerance(env: Env, tolerance: Option<u128>) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        match tolerance {
+            Some(tolerance) => env
+                .storage()
+                .persistent()
+                .set(&ConfigKey::PrecisionTolerance, &tolerance),
+            None => env
+                .storage()
+                .persistent()
+                .remove(&ConfigKey::PrecisionTolerance),
+        }
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("tolerance")),
+            tolerance,
+        );
+
+        Ok(())
+    }
+
+    /// Returns the Precision win tolerance, None if only exact guesses win
+    pub fn get_precision_tolerance(env: Env) -> Option<u128> {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::PrecisionTolerance)
+    }
+
+    /// Returns the minimum participants for a round to pay out (0 = no minimum)
+    pub fn get_min_participants_to_pay(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::MinParticipants)
+            .unwrap_or(0)
+    }
+
+    /// Returns user statistics (wins, losses, streaks)
+    pub fn get_user_stats(env: Env, user: Address) -> UserStats {
+        let key = DataKey::UserStats(user);
+        env.storage().persistent().get(&key).unwrap_or(UserStats {
+            total_wins: 0,
+            total_losses: 0,
+            current_streak: 0,
+            best_streak: 0,
+            biggest_win: 0,
+            total_staked: 0,
+            total_bets: 0,
+        })
+    }
+
+    /// Returns the total vXLM the user has ever staked, across all seasons
+    pub fn get_user_lifetime_volume(env: Env, user: Address) -> i128 {
+        Self::get_user_stats(env, user).total_staked
+    }
+
+    /// Returns the user's lifetime volume divided by their number of bets (0 before any bet)
+    pub fn get_user_avg_stake(env: Env, user: Address) -> i128 {
+        let stats = Self::get_user_stats(env, user);
+        if stats.total_bets == 0 {
+            return 0;
+        }
+        stats.total_staked / stats.total_bets as i128
+    }
+
+    /// Returns the user's balance, pending winnings, active-round position and stats in one read
+    pub fn get_account(env: Env, user: Address) -> AccountSnapshot {
+        let current_position =
+            if let Some(position) = Self::get_user_position(env.clone(), user.clone()) {
+                CurrentPosition::Side(position)
+            } else if let Some(prediction) =
+                Self::get_user_precision_prediction(env.clone(), user.clone())
+            {
+                CurrentPosition::Price(prediction)
+            } else {
+                CurrentPosition::None
+            };
+
+        AccountSnapshot {
+            balance: Self::balance(env.clone(), user.clone()),
+            pending_winnings: Self::get_pending_winnings(env.clone(), user.clone()),
+            current_position,
+            stats: Self::get_user_stats(env, user),
+        }
+    }
+
+    /// Returns user's claimable winnings
+    pub fn get_pending_winnings(env: Env, user: Address) -> i128 {
+        let key = DataKey::PendingWinnings(user);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Places a bet on the active round (Up/Down mode only)
+    /// With auto-claim enabled, pending winnings are moved into balance first
+    pub fn place_bet(
+        env: Env,
+        user: Address,
+        amount: i128,
+        side: BetSide,
+    ) -> Result<(), ContractError> {
+        user.require_auth();
+
+        // Expired winnings are left for the sweep rather than blocking the bet
+        if Self::get_auto_claim(env.clone(), user.clone()) {
+            Self::_claim_unexpired(&env, user.clone())?;
+        }
+
+        Self::_place_updown_bet(&env, user, amount, side)
+    }
+
+    /// Places as much of an Up/Down bet as fits under the pot cap
+    /// Only the accepted portion is deducted; returns that amount
+    pub fn place_bet_partial(
+        env: Env,
+        user: Address,
+        amount: i128,
+        side: BetSide,
+    ) -> Result<i128, ContractError> {
+        user.require_auth();
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        let cap = Self::get_max_pot(env.clone());
+        let accepted = if cap == 0 {
+            amount
+        } else {
+            let pot = round
+                .pool_up
+                .checked_add(round.pool_down)
+                .ok_or(ContractError::Overflow)?;
+            let room = cap.saturating_sub(pot);
+            if room <= 0 {
+                return Err(ContractError::PotCapExceeded);
+            }
+            amount.min(room)
+        };
+
+        Self::_place_updown_bet(&env, user, accepted, side)?;
+
+        Ok(accepted)
+    }
+
+    /// Validates and records an Up/Down bet; the caller must have checked auth
+    fn _place_updown_bet(
+        env: &Env,
+        user: Address,
+        amount: i128,
+        side: BetSide,
+    ) -> Result<(), ContractError> {
+        Self::_release_unlocked(env, &user)?;
+        let (mut round, mut positions, user_balance) =
+            Self::_check_updown_bet(env, user.clone(), amount)?;
+        Self::_consume_daily_limit(env, user.clone(), amount)?;
+
+        let new_balance = user_balance
+            .checked_sub(amount)
+            .ok_or(ContractError::Overflow)?;
+        Self::_set_balance(env, user.clone(), new_balance)?;
+
+        // The check only lets a position from an earlier round through; its stake goes back first
+        if let Some(stale) = positions.get(user.clone()) {
+            let refund = Payout {
+                user: user.clone(),
+                amount: stale.amount,
+                kind: PayoutKind::Refund,
+            };
+            Self::_apply_payout(env, &refund, stale.round_id)?;
+        }
+
+        let position = UserPosition {
+            amount,
+            side: side.clone(),
+            round_id: round.start_ledger,
+        };
+        positions.set(user.clone(), position);
+
+        match side {
+            BetSide::Up => {
+                round.pool_up = round
+                    .pool_up
+                    .checked_add(amount)
+                    .ok_or(ContractError::Overflow)?;
+            }
+            BetSide::Down => {
+                round.pool_down = round
+                    .pool_down
+                    .checked_add(amount)
+                    .ok_or(ContractError::Overflow)?;
+            }
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::UpDownPositions, &positions);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ActiveRound, &round);
+
+        Self::_record_bet(
+            env,
+            user.clone(),
+            BetRecord {
+                round_id: round.start_ledger,
+                amount,
+                choice: BetChoice::Side(side.clone()),
+            },
+        );
+
+        // Also keep legacy Positions storage for backwards compatibility
+        let mut legacy_positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Positions)
+            .unwrap_or(Map::new(env));
+        legacy_positions.set(
+            user,
+            UserPosition {
+                amount,
+                side,
+                round_id: round.start_ledger,
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::Positions, &legacy_positions);
+
+        Self::_record_volume(env, amount)?;
+
+        Ok(())
+    }
+
+    /// Dry-runs `place_bet` preconditions without changing state
+    /// Returns the first error place_bet would fail with, or Ok if the bet would go through
+    pub fn can_place_bet(env: Env, user: Address, amount: i128) -> Result<(), ContractError> {
+        Self::_check_updown_bet(&env, user.clone(), amount)?;
+        Self::_check_daily_limit(&env, user, amount)?;
+
+        Ok(())
+    }
+
+    /// Validates an Up/Down bet against the active round
+    /// Returns the round, its positions and the user's balance for the caller to update
+    fn _check_updown_bet(
+        env: &Env,
+        user: Address,
+        amount: i128,
+    ) -> Result<(Round, Map<Address, UserPosition>, i128), ContractError> {
+        Self::_check_not_blacklisted(env, &user)?;
+        Self::_check_not_oracle(env, &user)?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        // Verify round is in Up/Down mode
+        if round.mode != RoundMode::UpDown {
+            return Err(ContractError::WrongModeForPrediction);
+        }
+
+        let current_ledger = env.ledger().sequence();
+        if current_ledger >= round.bet_end_ledger {
+            return Err(ContractError::RoundEnded);
+        }
+
+        let user_balance = Self::_unlocked_balance(env, &user);
+        if user_balance < amount {
+            return Err(ContractError::InsufficientBalance);
+        }
+
+        // Use UpDownPositions storage for Up/Down mode
+        let positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UpDownPositions)
+            .unwrap_or(Map::new(env));
+
+        // A leftover position from an earlier round is no duplicate; placing the bet refunds it
+        if let Some(existing) = positions.get(user) {
+            if existing.round_id == round.start_ledger {
+                return Err(ContractError::AlreadyBet);
+            }
+        }
+
+        let pot = round
+            .pool_up
+            .checked_add(round.pool_down)
+            .ok_or(ContractError::Overflow)?;
+        Self::_check_pot_cap(env, pot, amount)?;
+        Self::_check_user_share(env, pot, amount)?;
+
+        Ok((round, positions, user_balance))
+    }
+
+    /// Places a precision prediction on the active round (Precision/Legends mode only)
+    /// predicted_price: price scaled to 4 decimals (e.g., 0.2297 → 2297)
+    pub fn place_precision_prediction(
+        env: Env,
+        user: Address,
+        amount: i128,
+        predicted_price: u128,
+    ) -> Result<(), ContractError> {
+        user.require_auth();
+
+        Self::_check_not_blacklisted(&env, &user)?;
+        Self::_check_not_oracle(&env, &user)?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
+
+        // Validate price scale (must be 4 decimal places, max value 9999 for 0.9999)
+        // Reasonable max: 99999999 (9999.9999 XLM)
+        if predicted_price > 99_999_999 {
+            return Err(ContractError::InvalidPriceScale);
+        }
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        // Verify round is in Precision mode
+        if round.mode != RoundMode::Precision {
+            return Err(ContractError::WrongModeForPrediction);
+        }
+
+        let current_ledger = env.ledger().sequence();
+        if current_ledger >= round.bet_end_ledger {
+            return Err(ContractError::RoundEnded);
+        }
+
+        Self::_release_unlocked(&env, &user)?;
+        let user_balance = Self::balance(env.clone(), user.clone());
+        if user_balance < amount {
+            return Err(ContractError::InsufficientBalance);
+        }
+
+        // Check if user already has a prediction in this round
+        let mut predictions: Map<Address, PrecisionPrediction> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PrecisionPositions)
+            .unwrap_or(Map::new(&env));
+
+        if predictions.contains_key(user.clone()) {
+            return Err(ContractError::AlreadyBet);
+        }
+
+        let mut pot: i128 = 0;
+        for prediction in predictions.values().iter() {
+            pot = pot
+                .checked_add(prediction.amount)
+                .ok_or(ContractError::Overflow)?;
+        }
+        Self::_check_pot_cap(&env, pot, amount)?;
+        Self::_check_user_share(&env, pot, amount)?;
+        Self::_consume_daily_limit(&env, user.clone(), amount)?;
+
+        // Deduct balance
+        let new_balance = user_balance
+            .checked_sub(amount)
+            .ok_or(ContractError::Overflow)?;
+        Self::_set_balance(&env, user.clone(), new_balance)?;
+
+        // Store prediction
+        let prediction = PrecisionPrediction {
+            user: user.clone(),
+            predicted_price,
+            amount,
+        };
+        predictions.set(user.clone(), prediction);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::PrecisionPositions, &predictions);
+
+        Self::_record_bet(
+            &env,
+            user.clone(),
+            BetRecord {
+                round_id: round.start_ledger,
+                amount,
+                choice: BetChoice::Price(predicted_price),
+            },
+        );
+
+        Self::_record_volume(&env, amount)?;
+
+        // Emit event for precision prediction
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("predict"), symbol_short!("price")),
+            (user, predicted_price, round.start_ledger),
+        );
+
+        Ok(())
+    }
+
+    /// Alias for place_precision_prediction - allows users to submit exact price predictions
+    /// guessed_price: price scaled to 4 decimals (e.g., 0.2297 → 2297)
+    pub fn predict_price(
+        env: Env,
+        user: Address,
+        guessed_price: u128,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        Self::place_precision_prediction(env, user, amount, guessed_price)
+    }
+
+    /// Returns user's position in the current round (Up/Down mode)
+    pub fn get_user_position(env: Env, user: Address) -> Option<UserPosition> {
+        let positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UpDownPositions)
+            .unwrap_or(Map::new(&env));
+
+        positions.get(user)
+    }
+
+    /// Returns what the user staked in a given round, including resolved ones
+    pub fn get_position_in_round(env: Env, user: Address, round_id: u32) -> Option<BetRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BetRecord(user, round_id))
+    }
+
+    /// Returns the ids of the rounds the user bet in, oldest first
+    /// Only the most recent MAX_USER_ROUNDS are kept
+    pub fn get_user_rounds(env: Env, user: Address) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UserRounds(user))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns results of resolved rounds in resolution order, starting at result id start_id
+    /// Result ids count resolutions from 0; missing ids are skipped and limit is capped at MAX_RESULTS_PAGE
+    pub fn get_round_results_page(env: Env, start_id: u32, limit: u32) -> Vec<RoundResult> {
+        let total_rounds: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TotalRounds)
+            .unwrap_or(0);
+        let end_id = start_id
+            .saturating_add(limit.min(MAX_RESULTS_PAGE))
+            .min(total_rounds);
+
+        let mut results = Vec::new(&env);
+        for id in start_id..end_id {
+            if let Some(result) = env.storage().persistent().get(&DataKey::RoundResult(id)) {
+                results.push_back(result);
+            }
+        }
+        results
+    }
+
+    /// Returns the most recently resolved round's (final_price - price_start, change in basis points)
+    /// None until a round has been resolved
+    pub fn get_last_result_delta(env: Env) -> Option<(i128, i32)> {
+        let total_rounds: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TotalRounds)
+            .unwrap_or(0);
+        let result: RoundResult = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RoundResult(total_rounds.checked_sub(1)?))?;
+
+        let start = i128::try_from(result.price_start).ok()?;
+        let delta = i128::try_from(result.final_price)
+            .ok()?
+            .checked_sub(start)?;
+        let change_bps = if start == 0 {
+            0
+        } else {
+            let bps = delta.checked_mul(10_000)? / start;
+            i32::try_from(bps).unwrap_or(if bps > 0 { i32::MAX } else { i32::MIN })
+        };
+
+        Some((delta, change_bps))
+    }
+
+    /// Stores the bet's history record, appends its round to the user's round list
+    /// and adds the stake to their lifetime volume
+    fn _record_bet(env: &Env, user: Address, record: BetRecord) {
+        let rounds_key = DataKey::UserRounds(user.clone());
+        let mut rounds: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&rounds_key)
+            .unwrap_or(Vec::new(env));
+        if rounds.len() >= MAX_USER_ROUNDS {
+            rounds.pop_front();
+        }
+        rounds.push_back(record.round_id);
+        env.storage().persistent().set(&rounds_key, &rounds);
+
+        let mut stats = Self::get_user_stats(env.clone(), user.clone());
+        stats.total_staked = stats.total_staked.saturating_add(record.amount);
+        stats.total_bets = stats.total_bets.saturating_add(1);
+        env.storage()
+            .persistent()
+            .set(&DataKey::UserStats(user.clone()), &stats);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::BetRecord(user, record.round_id), &record);
+    }
+
+    /// Returns user's precision prediction in the current round (Precision mode)
+    pub fn get_user_precision_prediction(env: Env, user: Address) -> Option<PrecisionPrediction> {
+        let predictions: Map<Address, PrecisionPrediction> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PrecisionPositions)
+            .unwrap_or(Map::new(&env));
+
+        predictions.get(user)
+    }
+
+    /// Returns (predicted price, total staked on it) for the active round, lowest price first
+    /// Limited to the MAX_LANDSCAPE_POINTS lowest prices; empty outside Precision mode
+    pub fn get_precision_landscape(env: Env) -> Vec<(u128, i128)> {
+        let mut stakes: Map<u128, i128> = Map::new(&env);
+        for prediction in Self::get_precision_predictions(env.clone()).iter() {
+            let staked = stakes.get(prediction.predicted_price).unwrap_or(0);
+            stakes.set(
+                prediction.predicted_price,
+                staked.saturating_add(prediction.amount),
+            );
+        }
+
+        let mut landscape = Vec::new(&env);
+        for (price, staked) in stakes.iter().take(MAX_LANDSCAPE_POINTS as usize) {
+            landscape.push_back((price, staked));
+        }
+        landscape
+    }
+
+    /// Returns all precision predictions for the current round
+    pub fn get_precision_predictions(env: Env) -> Vec<PrecisionPrediction> {
+        let predictions: Map<Address, PrecisionPrediction> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PrecisionPositions)
+            .unwrap_or(Map::new(&env));
+        predictions.values()
+    }
+
+    /// Returns whether the user has a bet or prediction in the active round
+    /// false if there is no active round; positions left over from other rounds don't count
+    pub fn has_bet(env: Env, user: Address) -> bool {
+        let round: Round = match env.storage().persistent().get(&DataKey::ActiveRound) {
+            Some(round) => round,
+            None => return false,
+        };
+
+        match round.mode {
+            RoundMode::UpDown => env
+                .storage()
+                .persistent()
+                .get::<_, Map<Address, UserPosition>>(&DataKey::UpDownPositions)
+                .and_then(|positions| positions.get(user))
+                .is_some_and(|position| position.round_id == round.start_ledger),
+            RoundMode::Precision => env
+                .storage()
+                .persistent()
+                .get::<_, Map<Address, PrecisionPrediction>>(&DataKey::PrecisionPositions)
+                .is_some_and(|predictions| predictions.contains_key(user)),
+        }
+    }
+
+    /// Returns how many positions the active round has: Up/Down bets or Precision predictions
+    /// 0 if there is no active round
+    pub fn get_position_count(env: Env) -> u32 {
+        let round: Round = match env.storage().persistent().get(&DataKey::ActiveRound) {
+            Some(round) => round,
+            None => return 0,
+        };
+
+        match round.mode {
+            RoundMode::UpDown => env
+                .storage()
+                .persistent()
+                .get::<_, Map<Address, UserPosition>>(&DataKey::UpDownPositions)
+                .map(|positions| positions.len())
+                .unwrap_or(0),
+            RoundMode::Precision => env
+                .storage()
+                .persistent()
+                .get::<_, Map<Address, PrecisionPrediction>>(&DataKey::PrecisionPositions)
+                .map(|predictions| predictions.len())
+                .unwrap_or(0),
+        }
+    }
+
+    /// Returns the active round's stake per side as (up, down)
+    /// Precision rounds have no sides: their whole pot is reported as (total, 0)
+    pub fn get_side_totals(env: Env) -> (i128, i128) {
+        let round: Round = match env.storage().persistent().get(&DataKey::ActiveRound) {
+            Some(round) => round,
+            None => return (0, 0),
+        };
+
+        match round.mode {
+            RoundMode::UpDown => (round.pool_up, round.pool_down),
+            RoundMode::Precision => {
+                let total = Self::get_precision_predictions(env)
+                    .iter()
+                    .fold(0i128, |total, pred| total.saturating_add(pred.amount));
+                (total, 0)
+            }
+        }
+    }
+
+    /// Returns the largest single UP and DOWN stakes in the active round as (up, down)
+    /// One pass over the round's positions; (0, 0) with no Up/Down round
+    pub fn get_largest_bets(env: Env) -> (i128, i128) {
+        let round_is_updown = env
+            .storage()
+            .persistent()
+            .get::<_, Round>(&DataKey::ActiveRound)
+            .is_some_and(|round| round.mode == RoundMode::UpDown);
+        if !round_is_updown {
+            return (0, 0);
+        }
+
+        let positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UpDownPositions)
+            .unwrap_or(Map::new(&env));
+
+        positions
+            .values()
+            .iter()
+            .fold((0, 0), |(up, down), position| match position.side {
+                BetSide::Up => (up.max(position.amount), down),
+                BetSide::Down => (up, down.max(position.amount)),
+            })
+    }
+
+    /// Returns how many distinct bettors are on each side of the active round as (up, down)
+    /// Positions are keyed by user, so each entry is one bettor; (0, 0) with no Up/Down round
+    pub fn get_side_participant_counts(env: Env) -> (u32, u32) {
+        let round_is_updown = env
+            .storage()
+            .persistent()
+            .get::<_, Round>(&DataKey::ActiveRound)
+            .is_some_and(|round| round.mode == RoundMode::UpDown);
+        if !round_is_updown {
+            return (0, 0);
+        }
+
+        let positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UpDownPositions)
+            .unwrap_or(Map::new(&env));
+
+        positions
+            .values()
+            .iter()
+            .fold((0, 0), |(up, down), position| match position.side {
+                BetSide::Up => (up + 1, down),
+                BetSide::Down => (up, down + 1),
+            })
+    }
+
+    /// Returns the active Up/Down round's payout multipliers as (up, down), in basis points
+    /// 10_000 = stake back only. The other side's pool counts net of consolation and
+    /// the house fee; a side nobody has bet on yet, or no Up/Down round, reports 0
+    pub fn get_net_odds(env: Env) -> (u32, u32) {
+        let round = match env
+            .storage()
+            .persistent()
+            .get::<_, Round>(&DataKey::ActiveRound)
+        {
+            Some(round) if round.mode == RoundMode::UpDown => round,
+            _ => return (0, 0),
+        };
+
+        (
+            Self::_net_multiplier(&env, round.pool_up, round.pool_down),
+            Self::_net_multiplier(&env, round.pool_down, round.pool_up),
+        )
+    }
+
+    /// Returns the multiplier, in basis points, an extra `amount` bet on `side` would earn
+    /// if the active Up/Down round resolved its way now, counting the bet in its own pool.
+    /// 10_000 while the other side is empty; 0 for a non-positive amount or no Up/Down round
+    pub fn quote_multiplier(env: Env, side: BetSide, amount: i128) -> u32 {
+        let round = match env
+            .storage()
+            .persistent()
+            .get::<_, Round>(&DataKey::ActiveRound)
+        {
+            Some(round) if round.mode == RoundMode::UpDown => round,
+            _ => return 0,
+        };
+        if amount <= 0 {
+            return 0;
+        }
+
+        let (own_pool, other_pool) = match side {
+            BetSide::Up => (round.pool_up, round.pool_down),
+            BetSide::Down => (round.pool_down, round.pool_up),
+        };
+        Self::_net_multiplier(&env, own_pool.saturating_add(amount), other_pool)
+    }
+
+    /// (own_pool + other_pool net of consolation and fee) / own_pool, in basis points
+    fn _net_multiplier(env: &Env, own_pool: i128, other_pool: i128) -> u32 {
+        if own_pool <= 0 {
+            return 0;
+        }
+        let consolation =
+            Self::_consolation_for(other_pool, Self::get_consolation_bps(env.clone())).unwrap_or(0);
+        let net_pool = other_pool.saturating_sub(consolation);
+        let net_pool = net_pool.saturating_sub(Self::_house_fee(env, net_pool).unwrap_or(0));
+        own_pool
+            .checked_add(net_pool)
+            .and_then(|total| Self::_mul_div(env, total, 10_000, own_pool).ok())
+            .map(|odds| u32::try_from(odds).unwrap_or(u32::MAX))
+            .unwrap_or(u32::MAX)
+    }
+
+    /// Breaks down what the user would be paid if the active Up/Down round resolved at final_price
+    /// final_payout = stake + raw_share + rollover_share + remainder_adjustment; nothing is written
+    pub fn explain_payout(
+        env: Env,
+        user: Address,
+        final_price: u128,
+    ) -> Result<PayoutExplanation, ContractError> {
+        let round = Self::get_active_round_checked(env.clone())?;
+        if round.mode != RoundMode::UpDown {
+            return Err(ContractError::WrongModeForPrediction);
+        }
+
+        // Leftover positions from earlier rounds would only be refunded
+        let positions = Self::_split_stale_positions(
+            &round,
+            Self::get_updown_positions(env.clone()),
+            &mut Vec::new(&env),
+        );
+        let position = positions.get(user.clone());
+        let stake = position.as_ref().map(|p| p.amount).unwrap_or(0);
+
+        let mut winning_pool: i128 = 0;
+        let mut losing_pool: i128 = 0;
+        let mut raw_share: i128 = 0;
+        let mut rollover_share: i128 = 0;
+        if let Some((side, winners, losers)) =
+            Self::_paying_side(&env, &round, final_price, positions.len())?
+        {
+            let (_, carried, pool) = Self::_pool_for_winners(&env, &positions, &side, losers)?;
+            winning_pool = winners;
+            losing_pool = pool.checked_sub(carried).ok_or(ContractError::Overflow)?;
+
+            if let Some(position) = &position {
+                if position.side == side {
+                    raw_share = Self::_mul_div(&env, stake, losing_pool, winning_pool)?;
+                    rollover_share = Self::_mul_div(&env, stake, carried, winning_pool)?;
+                }
+            }
+        }
+
+        // The payout itself comes from the same computation resolution uses
+        let final_payout = Self::_compute_payouts(&env, &round, final_price)?
+            .payouts
+            .iter()
+            .find(|payout| payout.user == user)
+            .map(|payout| payout.amount)
+            .unwrap_or(0);
+
+        let remainder_adjustment = final_payout
+            .checked_sub(stake)
+            .and_then(|r| r.checked_sub(raw_share))
+            .and_then(|r| r.checked_sub(rollover_share))
+            .ok_or(ContractError::Overflow)?;
+
+        Ok(PayoutExplanation {
+            stake,
+            winning_pool,
+            losing_pool,
+            raw_share,
+            rollover_share,
+            remainder_adjustment,
+            final_payout,
+        })
+    }
+
+    /// Counts the positions that would win if the active round resolved at hypothetical_price
+    /// Uses the same computation as resolution, so refunds count as no winners; nothing is
+    /// written. 0 with no active round
+    pub fn projected_winner_count(env: Env, hypothetical_price: u128) -> u32 {
+        let round: Round = match env.storage().persistent().get(&DataKey::ActiveRound) {
+            Some(round) => round,
+            None => return 0,
+        };
+        if hypothetical_price == 0 {
+            return 0;
+        }
+
+        match Self::_compute_payouts(&env, &round, hypothetical_price) {
+            Ok(computed) => computed
+                .payouts
+                .iter()
+                .filter(|payout| payout.kind == PayoutKind::Win)
+                .count() as u32,
+            Err(_) => 0,
+        }
+    }
+
+    /// Each winner's share of the losing pool if the active Up/Down round resolved at final_price
+    /// Shares exclude the winner's own stake and any carried-over pot; refunds and one-sided
+    /// rounds have no winners. Nothing is written
+    pub fn get_winner_shares(
+        env: Env,
+        final_price: u128,
+    ) -> Result<Vec<(Address, i128)>, ContractError> {
+        let round = Self::get_active_round_checked(env.clone())?;
+        if round.mode != RoundMode::UpDown {
+            return Err(ContractError::WrongModeForPrediction);
+        }
+
+        let positions = Self::_split_stale_positions(
+            &round,
+            Self::get_updown_positions(env.clone()),
+            &mut Vec::new(&env),
+        );
+        let mut shares = Vec::new(&env);
+        let (side, winning_pool, losing_pool) =
+            match Self::_paying_side(&env, &round, final_price, positions.len())? {
+                Some(paying) => paying,
+                None => return Ok(shares),
+            };
+
+        let (_, carried, pool) = Self::_pool_for_winners(&env, &positions, &side, losing_pool)?;
+        let losing_pool = pool.checked_sub(carried).ok_or(ContractError::Overflow)?;
+        let by_user = Self::_winner_shares(&env, &positions, &side, winning_pool, losing_pool)?;
+        for (user, position) in positions.iter() {
+            if position.side == side {
+                shares.push_back((user.clone(), by_user.get(user).unwrap_or(0)));
+            }
+        }
+
+        Ok(shares)
+    }
+
+    /// The side an Up/Down round would pay at final_price, with its (winning, losing) pools
+    /// None when the round would refund or void its stakes, or nobody backed the winning side
+    fn _paying_side(
+        env: &Env,
+        round: &Round,
+        final_price: u128,
+        participants: u32,
+    ) -> Result<Option<(BetSide, i128, i128)>, ContractError> {
+        let side = match Self::_updown_winning_side(env, round, final_price, participants)? {
+            Some(_) if Self::_one_sided_voided(env, round) => return Ok(None),
+            Some(side) => side,
+            None => return Ok(None),
+        };
+        let (winning_pool, losing_pool) = match side {
+            BetSide::Up => (round.pool_up, round.pool_down),
+            BetSide::Down => (round.pool_down, round.pool_up),
+        };
+        if winning_pool == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some((side, winning_pool, losing_pool)))
+    }
+
+    /// Returns all Up/Down positions for the current round
+    pub fn get_updown_positions(env: Env) -> Map<Address, UserPosition> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UpDownPositions)
+            .unwrap_or(Map::new(&env))
+    }
+
+    /// Resolves the round with oracle payload (oracle only)
+    /// Mode 0 (Up/Down): Winners split losers' pool proportionally; ties get refunds
+    /// Mode 1 (Precision/Legends): Closest guess wins full pot; ties split evenly
+    pub fn resolve_round(env: Env, payload: OraclePayload) -> Result<(), ContractError> {
+        let round = Self::_check_resolvable(&env, &payload)?;
+
+        Self::_settle_round(&env, &round, payload.price)
+    }
+
+    /// Resolves an Up/Down round like `resolve_round` but leaves paying its positions to `settle_batch` (oracle only)
+    pub fn resolve_round_batched(env: Env, payload: OraclePayload) -> Result<(), ContractError> {
+        let round = Self::_check_resolvable(&env, &payload)?;
+        if round.mode != RoundMode::UpDown {
+            return Err(ContractError::WrongModeForPrediction);
+        }
+
+        let settlement = Self::_start_settlement(&env, &round, payload.price)?;
+        if settlement.total > 0 {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Settlement, &settlement);
+            env.storage().persistent().set(
+                &DataKey::SettlementPositions,
+                &Self::get_updown_positions(env.clone()),
+            );
+        }
+
+        Self::_finish_round(&env, &round, payload.price)
+    }
+
+    /// Pays up to `limit` more positions of a batched resolution, losing ones included
+    /// Callable by anyone: the outcome was fixed when the round was resolved.
+    /// Returns the number of positions paid
+    pub fn settle_batch(env: Env, limit: u32) -> Result<u32, ContractError> {
+        let mut settlement: Settlement = match env.storage().persistent().get(&DataKey::Settlement)
+        {
+            Some(settlement) => settlement,
+            None => return Ok(0),
+        };
+        let positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SettlementPositions)
+            .unwrap_or(Map::new(&env));
+
+        let end = settlement
+            .settled
+            .saturating_add(limit)
+            .min(settlement.total);
+        let paid = end - settlement.settled;
+
+        // Walked from the end so rounding excess comes off the last winners, as in resolve_round
+        let users = positions.keys();
+        for i in settlement.settled..end {
+            let user = users.get_unchecked(settlement.total - 1 - i);
+            let position = positions.get_unchecked(user.clone());
+            if let Some(payout) = Self::_settlement_payout(&env, &mut settlement, user, position)? {
+                settlement.unpaid = settlement
+                    .unpaid
+                    .checked_sub(payout.amount)
+                    .ok_or(ContractError::Overflow)?;
+                Self::_apply_payout(&env, &payout, settlement.round_id)?;
+            }
+        }
+        settlement.settled = end;
+
+        if end < settlement.total {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Settlement, &settlement);
+            return Ok(paid);
+        }
+
+        env.storage().persistent().remove(&DataKey::Settlement);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::SettlementPositions);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("round"), symbol_short!("settled")),
+            settlement.round_id,
+        );
+
+        Ok(paid)
+    }
+
+    /// Returns (paid, total) positions of the batched resolution still in progress, (0, 0) if none
+    pub fn get_settlement_progress(env: Env) -> (u32, u32) {
+        match env
+            .storage()
+            .persistent()
+            .get::<_, Settlement>(&DataKey::Settlement)
+        {
+            Some(settlement) => (settlement.settled, settlement.total),
+            None => (0, 0),
+        }
+    }
+
+    /// Fixes how every position of the round is paid at final_price for `settle_batch`
+    /// The round-wide part, the house fee and the carried-over pot, is applied right away
+    fn _start_settlement(
+        env: &Env,
+        round: &Round,
+        final_price: u128,
+    ) -> Result<Settlement, ContractError> {
+        let positions = Self::get_updown_positions(env.clone());
+        let mut stale_refunds = Vec::new(env);
+        let current = Self::_split_stale_positions(round, positions.clone(), &mut stale_refunds);
+        Self::_check_pools(round, &current)?;
+
+        let mut unpaid = round
+            .pool_up
+            .checked_add(round.pool_down)
+            .ok_or(ContractError::Overflow)?;
+        for refund in stale_refunds.iter() {
+            unpaid = unpaid
+                .checked_add(refund.amount)
+                .ok_or(ContractError::Overflow)?;
+        }
+
+        let mut settlement = Settlement {
+            round_id: round.start_ledger,
+            final_price,
+            outcome: SettlementOutcome::Refund,
+            pool_up: round.pool_up,
+            pool_down: round.pool_down,
+            share_pool: 0,
+            excess: 0,
+            consolation_bps: 0,
+            refund_fee_bps: 0,
+            nearest: false,
+            unpaid: 0,
+            settled: 0,
+            total: positions.len(),
+        };
+        let mut round_wide = Vec::new(env);
+        let mut rollover_paid: i128 = 0;
+
+        match Self::_updown_winning_side(env, round, final_price, current.len())? {
+            None => {}
+            Some(_) if Self::_one_sided_voided(env, round) => {
+                if Self::get_one_sided_policy(env.clone()) == ONE_SIDED_ROLLOVER {
+                    let pot = round
+                        .pool_up
+                        .checked_add(round.pool_down)
+                        .ok_or(ContractError::Overflow)?;
+                    round_wide.push_back(Payout {
+                        user: env.current_contract_address(),
+                        amount: pot,
+                        kind: PayoutKind::Rollover,
+                    });
+                    settlement.outcome = SettlementOutcome::Void;
+                } else {
+                    let mut total_fee: i128 = 0;
+                    for position in current.values().iter() {
+                        total_fee = total_fee
+                            .checked_add(Self::_house_fee(env, position.amount)?)
+                            .ok_or(ContractError::Overflow)?;
+                    }
+                    round_wide.push_back(Payout {
+                        user: env.current_contract_address(),
+                        amount: total_fee,
+                        kind: PayoutKind::Fee,
+                    });
+                    settlement.refund_fee_bps = Self::get_fee_bps(env.clone());
+                }
+            }
+            Some(side) => {
+                let (winning_pool, losing_pool) = match side {
+                    BetSide::Up => (round.pool_up, round.pool_down),
+                    BetSide::Down => (round.pool_down, round.pool_up),
+                };
+                // As in resolve_round, a side nobody backed has no one to pay
+                if winning_pool == 0 {
+                    unpaid = unpaid
+                        .checked_sub(losing_pool)
+                        .ok_or(ContractError::Overflow)?;
+                    settlement.outcome = SettlementOutcome::Void;
+                } else {
+                    let (fee, carried, share_pool) =
+                        Self::_pool_for_winners(env, &current, &side, losing_pool)?;
+                    round_wide.push_back(Payout {
+                        user: env.current_contract_address(),
+                        amount: fee,
+                        kind: PayoutKind::Fee,
+                    });
+                    rollover_paid = carried;
+
+                    settlement.nearest = Self::get_rounding(env.clone()) == ROUNDING_NEAREST;
+                    if settlement.nearest {
+                        let mut distributed: i128 = 0;
+                        for position in current.values().iter() {
+                            if position.side == side {
+                                distributed = distributed
+                                    .checked_add(Self::_mul_div_nearest(
+                                        env,
+                                        position.amount,
+                                        share_pool,
+                                        winning_pool,
+                                    )?)
+                                    .ok_or(ContractError::Overflow)?;
+                            }
+                        }
+                        settlement.excess = distributed
+                            .checked_sub(share_pool)
+                            .ok_or(ContractError::Overflow)?
+                            .max(0);
+                    }
+                    settlement.outcome = SettlementOutcome::Win(side);
+                    settlement.share_pool = share_pool;
+                    settlement.consolation_bps = Self::get_consolation_bps(env.clone());
+                }
+            }
+        }
+
+        // The fee and stakes carried over never reach the positions; the carried pot they take does
+        for payout in round_wide.iter() {
+            if payout.amount == 0 {
+                continue;
+            }
+            unpaid = unpaid
+                .checked_sub(payout.amount)
+                .ok_or(ContractError::Overflow)?;
+            Self::_apply_payout(env, &payout, round.start_ledger)?;
+        }
+        Self::_adjust_rollover_pot(env, -rollover_paid)?;
+        settlement.unpaid = unpaid
+            .checked_add(rollover_paid)
+            .ok_or(ContractError::Overflow)?;
+
+        Ok(settlement)
+    }
+
+    /// What one position of a batched settlement is paid, if anything
+    /// Takes what is left of the rounding excess off a winning share first
+    fn _settlement_payout(
+        env: &Env,
+        settlement: &mut Settlement,
+        user: Address,
+        position: UserPosition,
+    ) -> Result<Option<Payout>, ContractError> {
+        // Left over from an earlier round: not part of this one
+        if position.round_id != settlement.round_id {
+            return Ok(Some(Payout {
+                user,
+                amount: position.amount,
+                kind: PayoutKind::Refund,
+            }));
+        }
+
+        let payout = match &settlement.outcome {
+            SettlementOutcome::Void => return Ok(None),
+            SettlementOutcome::Refund => {
+                let fee = position
+                    .amount
+                    .checked_mul(settlement.refund_fee_bps as i128)
+                    .ok_or(ContractError::Overflow)?
+                    / 10_000;
+                Payout {
+                    user,
+                    amount: position
+                        .amount
+                        .checked_sub(fee)
+                        .ok_or(ContractError::Overflow)?,
+                    kind: PayoutKind::Refund,
+                }
+            }
+            SettlementOutcome::Win(side) if position.side == *side => {
+                let winning_pool = match side {
+                    BetSide::Up => settlement.pool_up,
+                    BetSide::Down => settlement.pool_down,
+                };
+                let share = if settlement.nearest {
+                    Self::_mul_div_nearest(
+                        env,
+                        position.amount,
+                        settlement.share_pool,
+                        winning_pool,
+                    )?
                 } else {
-                    final_price
-                        .checked_sub(pred.predicted_price)
+                    Self::_mul_div(env, position.amount, settlement.share_pool, winning_pool)?
+                };
+                let taken = share.min(settlement.excess);
+                settlement.excess -= taken;
+                Payout {
+                    user,
+                    amount: position
+                        .amount
+                        .checked_add(share - taken)
+                        .ok_or(ContractError::Overflow)?,
+                    kind: PayoutKind::Win,
+                }
+            }
+            SettlementOutcome::Win(_) => Payout {
+                user,
+                amount: Self::_consolation_for(position.amount, settlement.consolation_bps)?,
+                kind: PayoutKind::Loss,
+            },
+        };
+
+        Ok(Some(payout))
+    }
+
+    /// Sets how many ledgers past end_ledger the oracle may take to resolve a round (admin only)
+    /// ledgers: 0 = no limit
+    pub fn set_oracle_max_ledger_lag(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::OracleMaxLedgerLag, &ledgers);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("max_lag")), ledgers);
+
+        Ok(())
+    }
+
+    /// Returns the oracle's resolution window in ledgers past end_ledger (0 = no limit)
+    pub fn get_oracle_max_ledger_lag(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::OracleMaxLedgerLag)
+            .unwrap_or(0)
+    }
+
+    /// Rejects a resolution arriving more than the allowed ledger lag after end_ledger
+    fn _check_oracle_ledger_lag(env: &Env, round: &Round) -> Result<(), ContractError> {
+        let lag = Self::get_oracle_max_ledger_lag(env.clone());
+        if lag > 0 && env.ledger().sequence().saturating_sub(round.end_ledger) > lag {
+            return Err(ContractError::OracleTooLate);
+        }
+
+        Ok(())
+    }
+
+    /// Checks the oracle payload against the active round and returns that round
+    fn _check_resolvable(env: &Env, payload: &OraclePayload) -> Result<Round, ContractError> {
+        if payload.price == 0 {
+            return Err(ContractError::InvalidPrice);
+        }
+
+        let oracle: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Oracle)
+            .ok_or(ContractError::OracleNotSet)?;
+
+        oracle.require_auth();
+
+        // A resubmitted resolution is reported as such, not as a missing round
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Resolved(payload.round_id))
+        {
+            return Err(ContractError::AlreadyResolved);
+        }
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        // Verify round ID matches to prevent cross-round replays
+        if payload.round_id != round.start_ledger {
+            return Err(ContractError::InvalidOracleRound);
+        }
+
+        Self::_check_oracle_freshness(env, payload.timestamp)?;
+
+        // Verify round has reached end_ledger
+        let current_ledger = env.ledger().sequence();
+        if current_ledger < round.end_ledger {
+            return Err(ContractError::RoundNotEnded);
+        }
+
+        Self::_check_oracle_ledger_lag(env, &round)?;
+
+        Ok(round)
+    }
+
+    /// Resolves the round against the time-weighted average of its checkpoints (oracle only)
+    /// Only the round's start-to-end window is averaged. Requires at least
+    /// MIN_TWAP_CHECKPOINTS in it, and the latest checkpoint must be fresh
+    pub fn resolve_round_twap(env: Env) -> Result<(), ContractError> {
+        let oracle: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Oracle)
+            .ok_or(ContractError::OracleNotSet)?;
+
+        oracle.require_auth();
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        let current_ledger = env.ledger().sequence();
+        if current_ledger < round.end_ledger {
+            return Err(ContractError::RoundNotEnded);
+        }
+
+        Self::_check_oracle_ledger_lag(&env, &round)?;
+
+        let checkpoints = Self::get_checkpoints(env.clone(), round.start_ledger);
+        if checkpoints.len() < MIN_TWAP_CHECKPOINTS {
+            return Err(ContractError::NotEnoughCheckpoints);
+        }
+
+        // The average must reach up to the end of the round
+        if let Some((_, last_timestamp)) = checkpoints.last() {
+            Self::_check_oracle_freshness(&env, last_timestamp)?;
+        }
+
+        let twap = Self::_compute_twap(
+            &checkpoints,
+            round.created_timestamp,
+            round.end_timestamp.min(env.ledger().timestamp()),
+        )?;
+
+        Self::_settle_round(&env, &round, twap)
+    }
+
+    /// Resolves the round at the price of its latest checkpoint (oracle only)
+    /// The checkpoint must be fresh, as for a pushed payload
+    pub fn resolve_from_last_checkpoint(env: Env) -> Result<(), ContractError> {
+        let oracle: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Oracle)
+            .ok_or(ContractError::OracleNotSet)?;
+
+        oracle.require_auth();
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        let current_ledger = env.ledger().sequence();
+        if current_ledger < round.end_ledger {
+            return Err(ContractError::RoundNotEnded);
+        }
+
+        Self::_check_oracle_ledger_lag(&env, &round)?;
+
+        let (price, timestamp) = Self::get_checkpoints(env.clone(), round.start_ledger)
+            .last()
+            .ok_or(ContractError::NotEnoughCheckpoints)?;
+        Self::_check_oracle_freshness(&env, timestamp)?;
+
+        Self::_settle_round(&env, &round, price)
+    }
+
+    /// Returns the current time-weighted average price of the active round's checkpoints
+    /// None if round_id is not the active round or it has fewer than MIN_TWAP_CHECKPOINTS
+    pub fn get_twap(env: Env, round_id: u32) -> Option<u128> {
+        let round: Round = env.storage().persistent().get(&DataKey::ActiveRound)?;
+        if round.start_ledger != round_id {
+            return None;
+        }
+        let checkpoints = Self::get_checkpoints(env.clone(), round_id);
+        if checkpoints.len() < MIN_TWAP_CHECKPOINTS {
+            return None;
+        }
+
+        Self::_compute_twap(
+            &checkpoints,
+            round.created_timestamp,
+            round.end_timestamp.min(env.ledger().timestamp()),
+        )
+        .ok()
+    }
+
+    /// Time-weighted average over [from, until]: each price is weighted by how long it stayed
+    /// the latest sample within that window, the last one up to `until`.
+    /// Samples after `until` are left out; fails with `NotEnoughCheckpoints` if too few remain
+    fn _compute_twap(
+        checkpoints: &Vec<(u128, u64)>,
+        from: u64,
+        until: u64,
+    ) -> Result<u128, ContractError> {
+        let mut weighted_sum: u128 = 0;
+        let mut total_weight: u128 = 0;
+        let mut price_sum: u128 = 0;
+        let mut samples: u32 = 0;
+
+        for (i, (price, timestamp)) in checkpoints.iter().enumerate() {
+            if timestamp > until {
+                break;
+            }
+            let next = match checkpoints.get(i as u32 + 1) {
+                Some((_, next_timestamp)) => next_timestamp.min(until),
+                None => until,
+            };
+            let weight = next.saturating_sub(timestamp.max(from)) as u128;
+
+            weighted_sum = price
+                .checked_mul(weight)
+                .and_then(|w| weighted_sum.checked_add(w))
+                .ok_or(ContractError::Overflow)?;
+            total_weight = total_weight
+                .checked_add(weight)
+                .ok_or(ContractError::Overflow)?;
+            price_sum = price_sum
+                .checked_add(price)
+                .ok_or(ContractError::Overflow)?;
+            samples += 1;
+        }
+        if samples < MIN_TWAP_CHECKPOINTS {
+            return Err(ContractError::NotEnoughCheckpoints);
+        }
+
+        // All samples share a timestamp: fall back to a plain average
+        if total_weight == 0 {
+            return Ok(price_sum / samples as u128);
+        }
+
+        Ok(weighted_sum / total_weight)
+    }
+
+    /// Pays out the active round at final_price, clears its storage and emits the result
+    fn _settle_round(env: &Env, round: &Round, final_price: u128) -> Result<(), ContractError> {
+        let computed = Self::_compute_payouts(env, round, final_price)?;
+        for payout in computed.payouts.iter() {
+            Self::_apply_payout(env, &payout, round.start_ledger)?;
+        }
+        Self::_adjust_rollover_pot(env, -computed.rollover_paid)?;
+
+        Self::_finish_round(env, round, final_price)
+    }
+
+    /// Works out what every position in the round is owed at final_price
+    fn _compute_payouts(
+        env: &Env,
+        round: &Round,
+        final_price: u128,
+    ) -> Result<RoundPayouts, ContractError> {
+        let mut payouts = Vec::new(env);
+
+        // Branch based on round mode
+        let rollover_paid = match round.mode {
+            RoundMode::UpDown => Self::_resolve_updown_mode(env, round, final_price, &mut payouts)?,
+            RoundMode::Precision => {
+                Self::_resolve_precision_mode(env, round, final_price, &mut payouts)?;
+                let rollover_paid;
+                (payouts, rollover_paid) = Self::_add_precision_rollover(env, payouts)?;
+                payouts = Self::_cap_precision_wins(env, payouts)?;
+                rollover_paid
+            }
+        };
+
+        Ok(RoundPayouts {
+            payouts,
+            rollover_paid,
+        })
+    }
+
+    /// Hands the rollover pot to the Precision winners, pro rata to what they won
+    /// Rounding dust goes to the last winner; with no winners the pot carries on.
+    /// Returns the payouts with the pot added and how much of it they took
+    fn _add_precision_rollover(
+        env: &Env,
+        payouts: Vec<Payout>,
+    ) -> Result<(Vec<Payout>, i128), ContractError> {
+        let carried = Self::get_rollover_pot(env.clone());
+        let mut total_won: i128 = 0;
+        let mut last_win: Option<u32> = None;
+        for (i, payout) in payouts.iter().enumerate() {
+            if payout.kind == PayoutKind::Win {
+                total_won = total_won
+                    .checked_add(payout.amount)
+                    .ok_or(ContractError::Overflow)?;
+                last_win = Some(i as u32);
+            }
+        }
+        let last_win = match last_win {
+            Some(i) if carried > 0 && total_won > 0 => i,
+            _ => return Ok((payouts, 0)),
+        };
+
+        let mut with_rollover = Vec::new(env);
+        let mut distributed: i128 = 0;
+        for (i, mut payout) in payouts.iter().enumerate() {
+            if payout.kind == PayoutKind::Win {
+                let share = if i as u32 == last_win {
+                    carried
+                        .checked_sub(distributed)
                         .ok_or(ContractError::Overflow)?
+                } else {
+                    Self::_mul_div(env, carried, payout.amount, total_won)?
                 };
+                distributed = distributed
+                    .checked_add(share)
+                    .ok_or(ContractError::Overflow)?;
+                payout.amount = payout
+                    .amount
+                    .checked_add(share)
+                    .ok_or(ContractError::Overflow)?;
+            }
+            with_rollover.push_back(payout);
+        }
+
+        Ok((with_rollover, carried))
+    }
+
+    /// Cuts every Precision win down to get_precision_max_payout
+    /// Each cut becomes a Fee or Rollover payout naming the capped winner
+    fn _cap_precision_wins(env: &Env, payouts: Vec<Payout>) -> Result<Vec<Payout>, ContractError> {
+        let cap = Self::get_precision_max_payout(env.clone());
+        if cap == 0 {
+            return Ok(payouts);
+        }
+        let excess_kind = if Self::get_payout_cap_policy(env.clone()) == PAYOUT_CAP_ROLLOVER {
+            PayoutKind::Rollover
+        } else {
+            PayoutKind::Fee
+        };
+
+        let mut capped = Vec::new(env);
+        for payout in payouts.iter() {
+            if payout.kind != PayoutKind::Win || payout.amount <= cap {
+                capped.push_back(payout);
+                continue;
+            }
+
+            let excess = payout
+                .amount
+                .checked_sub(cap)
+                .ok_or(ContractError::Overflow)?;
+            capped.push_back(Payout {
+                user: payout.user.clone(),
+                amount: cap,
+                kind: PayoutKind::Win,
+            });
+            capped.push_back(Payout {
+                user: payout.user,
+                amount: excess,
+                kind: excess_kind.clone(),
+            });
+        }
+
+        Ok(capped)
+    }
+
+    /// Credits a payout to the user's pending winnings and updates their stats
+    fn _apply_payout(env: &Env, payout: &Payout, round_id: u32) -> Result<(), ContractError> {
+        match payout.kind {
+            PayoutKind::Win => {
+                Self::_credit_pending(env, payout.user.clone(), payout.amount, round_id)?;
+                Self::_update_stats_win(env, payout.user.clone(), payout.amount);
+            }
+            PayoutKind::Loss => {
+                // Losers may still be owed a consolation
+                if payout.amount > 0 {
+                    Self::_credit_pending(env, payout.user.clone(), payout.amount, round_id)?;
+                }
+                Self::_update_stats_loss(env, payout.user.clone());
+            }
+            PayoutKind::Refund => {
+                Self::_credit_pending(env, payout.user.clone(), payout.amount, round_id)?;
+
+                #[allow(deprecated)]
+                env.events().publish(
+                    (symbol_short!("refund"), payout.user.clone()),
+                    payout.amount,
+                );
+            }
+            PayoutKind::Fee => {
+                Self::_credit_treasury(env, payout.amount)?;
+            }
+            PayoutKind::Rollover => {
+                Self::_adjust_rollover_pot(env, payout.amount)?;
+            }
+        }
+
+        // Fee and Rollover payouts name the contract unless they hold a capped winner's excess
+        let is_cap_excess = matches!(payout.kind, PayoutKind::Fee | PayoutKind::Rollover)
+            && payout.user != env.current_contract_address();
+        if is_cap_excess {
+            #[allow(deprecated)]
+            env.events().publish(
+                (symbol_short!("capped"), payout.user.clone()),
+                payout.amount,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Applies a change to the carried-over pot: positive carries vXLM over, negative pays it out
+    fn _adjust_rollover_pot(env: &Env, delta: i128) -> Result<(), ContractError> {
+        if delta == 0 {
+            return Ok(());
+        }
+        let pot = Self::get_rollover_pot(env.clone())
+            .checked_add(delta)
+            .ok_or(ContractError::Overflow)?;
+        env.storage().persistent().set(&DataKey::RolloverPot, &pot);
+
+        Ok(())
+    }
+
+    /// Removes round results that fall outside the history limit, oldest first
+    fn _prune_history(env: &Env, total_rounds: u32) {
+        let keep_from = total_rounds.saturating_sub(Self::get_history_limit(env.clone()));
+        let mut oldest: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::HistoryStart)
+            .unwrap_or(0);
+        let stop = keep_from.min(oldest.saturating_add(MAX_HISTORY_PRUNE));
+        if oldest >= stop {
+            return;
+        }
+
+        while oldest < stop {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::RoundResult(oldest));
+            oldest += 1;
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::HistoryStart, &oldest);
+    }
+
+    /// Counts the active round as resolved, clears its storage and emits the result
+    fn _finish_round(env: &Env, round: &Round, final_price: u128) -> Result<(), ContractError> {
+        let total_rounds: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TotalRounds)
+            .unwrap_or(0);
+        env.storage().persistent().set(
+            &DataKey::RoundResult(total_rounds),
+            &RoundResult {
+                round_id: round.start_ledger,
+                mode: round.mode.clone(),
+                price_start: round.price_start,
+                final_price,
+                pool_up: round.pool_up,
+                pool_down: round.pool_down,
+                resolved_ledger: env.ledger().sequence(),
+            },
+        );
+        let total_rounds = total_rounds.checked_add(1).ok_or(ContractError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::TotalRounds, &total_rounds);
+        Self::_prune_history(env, total_rounds);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::LastResolvedLedger, &env.ledger().sequence());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Resolved(round.start_ledger), &true);
+
+        // Precision rounds report their whole pot as the first total
+        let (up, down) = Self::get_side_totals(env.clone());
+        let pot = up.checked_add(down).ok_or(ContractError::Overflow)?;
+        if pot > Self::get_biggest_pot(env.clone()) {
+            env.storage().persistent().set(&DataKey::BiggestPot, &pot);
+        }
+
+        // Keep the result around for the reveal window if one is configured
+        if Self::get_result_retention(env.clone()) > 0 {
+            let mut resolved_round = round.clone();
+            resolved_round.resolved = true;
+            resolved_round.final_price = final_price;
+            env.storage()
+                .persistent()
+                .set(&DataKey::ResolvedRound, &resolved_round);
+        }
+
+        // Clean up storage
+        env.storage().persistent().remove(&DataKey::ActiveRound);
+        env.storage().persistent().remove(&DataKey::Positions);
+        env.storage().persistent().remove(&DataKey::UpDownPositions);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PrecisionPositions);
+
+        // Emit resolution event
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("round"), symbol_short!("resolved")),
+            final_price,
+        );
+
+        Ok(())
+    }
+
+    /// Records an intra-round price sample for the active round (oracle only)
+    /// Only the most recent MAX_CHECKPOINTS samples are kept. With no round open the
+    /// sample only becomes the latest checkpoint, which the next round's start price is checked against
+    pub fn push_checkpoint(env: Env, price: u128, timestamp: u64) -> Result<(), ContractError> {
+        if price == 0 {
+            return Err(ContractError::InvalidPrice);
+        }
+
+        let oracle: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Oracle)
+            .ok_or(ContractError::OracleNotSet)?;
+
+        oracle.require_auth();
+
+        Self::_check_oracle_freshness(&env, timestamp)?;
+
+        let round: Round = match env.storage().persistent().get(&DataKey::ActiveRound) {
+            Some(round) => round,
+            None => return Self::_push_start_checkpoint(&env, price, timestamp),
+        };
+
+        let key = DataKey::Checkpoints(round.start_ledger);
+        let mut checkpoints: Vec<(u128, u64)> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+
+        // Samples must arrive in time order for the TWAP weighting
+        if let Some((_, last_timestamp)) = checkpoints.last() {
+            if timestamp < last_timestamp {
+                return Err(ContractError::StaleOracleData);
+            }
+        }
+
+        if checkpoints.len() >= MAX_CHECKPOINTS {
+            checkpoints.pop_front();
+        }
+        checkpoints.push_back((price, timestamp));
+        env.storage().persistent().set(&key, &checkpoints);
+        env.storage()
+            .persistent()
+            .set(&DataKey::LastCheckpoint, &(price, timestamp));
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("oracle"), symbol_short!("checkpt")),
+            (round.start_ledger, price, timestamp),
+        );
+
+        Ok(())
+    }
+
+    /// Records a between-rounds sample as the latest checkpoint only
+    fn _push_start_checkpoint(env: &Env, price: u128, timestamp: u64) -> Result<(), ContractError> {
+        if let Some((_, last_timestamp)) = Self::get_last_checkpoint(env.clone()) {
+            if timestamp < last_timestamp {
+                return Err(ContractError::StaleOracleData);
+            }
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::LastCheckpoint, &(price, timestamp));
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("oracle"), symbol_short!("startpx")),
+            (price, timestamp),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the (price, timestamp) checkpoints pushed for a round
+    pub fn get_checkpoints(env: Env, round_id: u32) -> Vec<(u128, u64)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Checkpoints(round_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns the latest (price, timestamp) checkpoint pushed in any round, if any
+    pub fn get_last_checkpoint(env: Env) -> Option<(u128, u64)> {
+        env.storage().persistent().get(&DataKey::LastCheckpoint)
+    }
+
+    /// Rejects oracle data older than ORACLE_MAX_AGE_SECONDS (5 minutes) or from the future
+    fn _check_oracle_freshness(env: &Env, timestamp: u64) -> Result<(), ContractError> {
+        let current_time = env.ledger().timestamp();
+        if timestamp > current_time {
+            return Err(ContractError::OracleTimestampInFuture);
+        }
+        if current_time > timestamp.saturating_add(ORACLE_MAX_AGE_SECONDS) {
+            return Err(ContractError::StaleOracleData);
+        }
+
+        Ok(())
+    }
+
+    /// Resolves Up/Down mode round
+    /// Returns how much of the carried-over pot the winners take
+    fn _resolve_updown_mode(
+        env: &Env,
+        round: &Round,
+        final_price: u128,
+        payouts: &mut Vec<Payout>,
+    ) -> Result<i128, ContractError> {
+        let positions =
+            Self::_split_stale_positions(round, Self::get_updown_positions(env.clone()), payouts);
+
+        Self::_check_pools(round, &positions)?;
+
+        let rollover_paid =
+            match Self::_updown_winning_side(env, round, final_price, positions.len())? {
+                None => {
+                    Self::_record_refunds(round, positions, payouts)?;
+                    0
+                }
+                Some(_) if Self::_one_sided_voided(env, round) => {
+                    Self::_record_one_sided(env, round, positions, payouts)?;
+                    0
+                }
+                Some(BetSide::Up) => Self::_record_winnings(
+                    env,
+                    positions,
+                    payouts,
+                    BetSide::Up,
+                    round.pool_up,
+                    round.pool_down,
+                )?,
+                Some(BetSide::Down) => Self::_record_winnings(
+                    env,
+                    positions,
+                    payouts,
+                    BetSide::Down,
+                    round.pool_down,
+                    round.pool_up,
+                )?,
+            };
+
+        Ok(rollover_paid)
+    }
+
+    /// Pools must equal the positions they were built from
+    fn _check_pools(
+        round: &Round,
+        positions: &Map<Address, UserPosition>,
+    ) -> Result<(), ContractError> {
+        let mut sum_up: i128 = 0;
+        let mut sum_down: i128 = 0;
+        for position in positions.values().iter() {
+            let pool = match position.side {
+                BetSide::Up => &mut sum_up,
+                BetSide::Down => &mut sum_down,
+            };
+            *pool = pool
+                .checked_add(position.amount)
+                .ok_or(ContractError::Overflow)?;
+        }
+        if sum_up != round.pool_up || sum_down != round.pool_down {
+            return Err(ContractError::PoolMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Whether the round has stakes on only one side and the policy voids such rounds
+    fn _one_sided_voided(env: &Env, round: &Round) -> bool {
+        (round.pool_up == 0 || round.pool_down == 0)
+            && Self::get_one_sided_policy(env.clone()) != ONE_SIDED_REFUND
+    }
+
+    /// Voids a one-sided round per the configured policy
+    /// Nobody wins or loses: stakes are refunded minus the house fee, or all carried over
+    fn _record_one_sided(
+        env: &Env,
+        round: &Round,
+        positions: Map<Address, UserPosition>,
+        payouts: &mut Vec<Payout>,
+    ) -> Result<(), ContractError> {
+        if Self::get_one_sided_policy(env.clone()) == ONE_SIDED_ROLLOVER {
+            let pot = round
+                .pool_up
+                .checked_add(round.pool_down)
+                .ok_or(ContractError::Overflow)?;
+            payouts.push_back(Payout {
+                user: env.current_contract_address(),
+                amount: pot,
+                kind: PayoutKind::Rollover,
+            });
+            return Ok(());
+        }
+
+        let mut total_fee: i128 = 0;
+        for (user, position) in positions.iter() {
+            let fee = Self::_house_fee(env, position.amount)?;
+            total_fee = total_fee.checked_add(fee).ok_or(ContractError::Overflow)?;
+            payouts.push_back(Payout {
+                user,
+                amount: position
+                    .amount
+                    .checked_sub(fee)
+                    .ok_or(ContractError::Overflow)?,
+                kind: PayoutKind::Refund,
+            });
+        }
+        if total_fee > 0 {
+            payouts.push_back(Payout {
+                user: env.current_contract_address(),
+                amount: total_fee,
+                kind: PayoutKind::Fee,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the side that wins an Up/Down round at final_price
+    /// None when everyone is refunded: the move stayed within the draw band
+    /// or fewer than the minimum participants took part
+    fn _updown_winning_side(
+        env: &Env,
+        round: &Round,
+        final_price: u128,
+        participants: u32,
+    ) -> Result<Option<BetSide>, ContractError> {
+        // Moves within the draw band count as unchanged (band of 0 = exact match only)
+        let draw_band_bps: u32 = env
+            .storage()
+            .persistent()
+            .get(&ConfigKey::DrawBandBps)
+            .unwrap_or(0);
+        let draw_band = round
+            .price_start
+            .checked_mul(draw_band_bps as u128)
+            .ok_or(ContractError::Overflow)?
+            / 10_000;
+        let price_move = final_price.abs_diff(round.price_start);
+
+        // Too few participants: nobody wins, everyone is refunded
+        let min_participants = Self::get_min_participants_to_pay(env.clone());
+        if price_move <= draw_band || participants < min_participants {
+            return Ok(None);
+        }
+
+        if final_price > round.price_start {
+            Ok(Some(BetSide::Up))
+        } else {
+            Ok(Some(BetSide::Down))
+        }
+    }
+
+    /// Resolves Precision/Legends mode round
+    /// Awards full pot to closest guess(es); ties split evenly
+    fn _resolve_precision_mode(
+        env: &Env,
+        round: &Round,
+        final_price: u128,
+        payouts: &mut Vec<Payout>,
+    ) -> Result<(), ContractError> {
+        let predictions_map: Map<Address, PrecisionPrediction> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PrecisionPositions)
+            .unwrap_or(Map::new(env));
+        let predictions = predictions_map.values();
+
+        // If no predictions, nothing to resolve
+        if predictions.is_empty() {
+            return Ok(());
+        }
+
+        // Too few participants: nobody wins, everyone is refunded
+        if predictions.len() < Self::get_min_participants_to_pay(env.clone()) {
+            Self::_record_precision_refunds(&predictions, payouts);
+            return Ok(());
+        }
+
+        let distance_mode = Self::get_precision_distance_mode(env.clone());
+
+        // No guess close enough: nobody wins, everyone is refunded. Without a tolerance the
+        // closest-guess modes take exact guesses only; zones and inverse distance have no limit
+        let payout_mode = Self::get_precision_payout_mode(env.clone());
+        let tolerance = match Self::get_precision_tolerance(env.clone()) {
+            Some(tolerance) => tolerance,
+            None if payout_mode == PRECISION_PAYOUT_CLOSEST
+                || payout_mode == PRECISION_PAYOUT_CLOSEST_DRAW =>
+            {
+                0
+            }
+            None => u128::MAX,
+        };
+        let closest = predictions
+            .iter()
+            .map(|pred| Self::_precision_distance(distance_mode, pred.predicted_price, final_price))
+            .min()
+            .unwrap_or(u128::MAX);
+        if closest > tolerance {
+            Self::_record_precision_refunds(&predictions, payouts);
+            return Ok(());
+        }
+
+        if payout_mode == PRECISION_PAYOUT_INVERSE_DISTANCE {
+            return Self::_record_inverse_distance(
+                env,
+                &predictions,
+                final_price,
+                distance_mode,
+                payouts,
+            );
+        }
+
+        if payout_mode == PRECISION_PAYOUT_ZONE {
+            return Self::_record_zone_winners(env, &predictions, final_price, payouts);
+        }
+
+        // Find minimum difference and collect all winners
+        let mut min_diff: Option<u128> = None;
+        let mut winners: Vec<PrecisionPrediction> = Vec::new(env);
+
+        for i in 0..predictions.len() {
+            if let Some(pred) = predictions.get(i) {
+                let diff =
+                    Self::_precision_distance(distance_mode, pred.predicted_price, final_price);
+
+                match min_diff {
+                    None => {
+                        // First prediction
+                        min_diff = Some(diff);
+                        winners.push_back(pred.clone());
+                    }
+                    Some(current_min) => {
+                        if diff < current_min {
+                            // New winner found, clear previous winners
+                            min_diff = Some(diff);
+                            winners = Vec::new(env);
+                            winners.push_back(pred.clone());
+                        } else if diff == current_min {
+                            // Tie - add to winners
+                            winners.push_back(pred.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        if payout_mode == PRECISION_PAYOUT_CLOSEST_DRAW && winners.len() > 1 {
+            let drawn = Self::_draw_tie_winner(env, round, &winners);
+            winners = Vec::from_array(env, [drawn]);
+        }
+
+        // Calculate total pot
+        let mut total_pot: i128 = 0;
+        for i in 0..predictions.len() {
+            if let Some(pred) = predictions.get(i) {
+                total_pot = total_pot
+                    .checked_add(pred.amount)
+                    .ok_or(ContractError::Overflow)?;
+            }
+        }
+
+        // Distribute winnings to winner(s)
+        if !winners.is_empty() && total_pot > 0 {
+            let winner_count = winners.len() as i128;
+            let payout_per_winner = total_pot / winner_count;
+            let remainder = total_pot % winner_count;
+
+            // Award to each winner
+            for i in 0..winners.len() {
+                if let Some(winner) = winners.get(i) {
+                    // First winner gets the remainder (if any)
+                    let payout = if i == 0 {
+                        payout_per_winner
+                            .checked_add(remainder)
+                            .ok_or(ContractError::Overflow)?
+                    } else {
+                        payout_per_winner
+                    };
+
+                    payouts.push_back(Payout {
+                        user: winner.user.clone(),
+                        amount: payout,
+                        kind: PayoutKind::Win,
+                    });
+                }
+            }
+
+            // Update stats for losers
+            for i in 0..predictions.len() {
+                if let Some(pred) = predictions.get(i) {
+                    let is_winner = winners.iter().any(|w| w.user == pred.user);
+                    if !is_winner {
+                        payouts.push_back(Payout {
+                            user: pred.user.clone(),
+                            amount: 0,
+                            kind: PayoutKind::Loss,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Splits the whole pot by stake among predictions in the same zone as the final price
+    /// Rounding dust goes to the last zone winner; with nobody in the zone every stake is refunded
+    fn _record_zone_winners(
+        env: &Env,
+        predictions: &Vec<PrecisionPrediction>,
+        final_price: u128,
+        payouts: &mut Vec<Payout>,
+    ) -> Result<(), ContractError> {
+        let boundaries = Self::get_precision_zones(env.clone());
+        if boundaries.is_empty() {
+            return Err(ContractError::ZoneNotConfigured);
+        }
+        let zone_of = |price: u128| boundaries.iter().filter(|b| *b <= price).count();
+        let winning_zone = zone_of(final_price);
+
+        let mut total_pot: i128 = 0;
+        let mut zone_stake: i128 = 0;
+        let mut last_winner: Option<u32> = None;
+        for (i, pred) in predictions.iter().enumerate() {
+            total_pot = total_pot
+                .checked_add(pred.amount)
+                .ok_or(ContractError::Overflow)?;
+            if zone_of(pred.predicted_price) == winning_zone {
+                zone_stake = zone_stake
+                    .checked_add(pred.amount)
+                    .ok_or(ContractError::Overflow)?;
+                last_winner = Some(i as u32);
+            }
+        }
+
+        let last_winner = match last_winner {
+            Some(i) if zone_stake > 0 => i,
+            _ => {
+                Self::_record_precision_refunds(predictions, payouts);
+                return Ok(());
+            }
+        };
+
+        let mut distributed: i128 = 0;
+        for (i, pred) in predictions.iter().enumerate() {
+            if zone_of(pred.predicted_price) != winning_zone {
+                payouts.push_back(Payout {
+                    user: pred.user,
+                    amount: 0,
+                    kind: PayoutKind::Loss,
+                });
+                continue;
+            }
+
+            let amount = if i as u32 == last_winner {
+                total_pot
+                    .checked_sub(distributed)
+                    .ok_or(ContractError::Overflow)?
+            } else {
+                Self::_mul_div(env, pred.amount, total_pot, zone_stake)?
+            };
+            distributed = distributed
+                .checked_add(amount)
+                .ok_or(ContractError::Overflow)?;
+            payouts.push_back(Payout {
+                user: pred.user,
+                amount,
+                kind: PayoutKind::Win,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Splits the whole pot across all predictions by stake / (1 + distance)
+    /// Rounding dust goes to the closest guess so the pot is paid out exactly
+    fn _record_inverse_distance(
+        env: &Env,
+        predictions: &Vec<PrecisionPrediction>,
+        final_price: u128,
+        distance_mode: u32,
+        payouts: &mut Vec<Payout>,
+    ) -> Result<(), ContractError> {
+        let mut weights: Vec<i128> = Vec::new(env);
+        let mut total_weight: i128 = 0;
+        let mut total_pot: i128 = 0;
+        let mut closest: u32 = 0;
+        let mut closest_distance = u128::MAX;
+
+        for (i, pred) in predictions.iter().enumerate() {
+            let distance =
+                Self::_precision_distance(distance_mode, pred.predicted_price, final_price);
+            if distance < closest_distance {
+                closest_distance = distance;
+                closest = i as u32;
+            }
+
+            let divisor = i128::try_from(distance.saturating_add(1)).unwrap_or(i128::MAX);
+            let weight = pred
+                .amount
+                .checked_mul(WEIGHT_SCALE)
+                .ok_or(ContractError::Overflow)?
+                / divisor;
+            weights.push_back(weight);
+
+            total_weight = total_weight
+                .checked_add(weight)
+                .ok_or(ContractError::Overflow)?;
+            total_pot = total_pot
+                .checked_add(pred.amount)
+                .ok_or(ContractError::Overflow)?;
+        }
+
+        // Every guess is too far off to carry any weight: refund
+        if total_weight == 0 {
+            for pred in predictions.iter() {
+                payouts.push_back(Payout {
+                    user: pred.user,
+                    amount: pred.amount,
+                    kind: PayoutKind::Refund,
+                });
+            }
+            return Ok(());
+        }
+
+        let mut shares: Vec<i128> = Vec::new(env);
+        let mut distributed: i128 = 0;
+        for weight in weights.iter() {
+            let share = total_pot
+                .checked_mul(weight)
+                .ok_or(ContractError::Overflow)?
+                / total_weight;
+            shares.push_back(share);
+            distributed = distributed
+                .checked_add(share)
+                .ok_or(ContractError::Overflow)?;
+        }
+
+        let dust = total_pot
+            .checked_sub(distributed)
+            .ok_or(ContractError::Overflow)?;
+
+        for (i, pred) in predictions.iter().enumerate() {
+            let mut share = shares.get(i as u32).unwrap_or(0);
+            if i as u32 == closest {
+                share = share.checked_add(dust).ok_or(ContractError::Overflow)?;
+            }
+
+            // Earning back more than the stake counts as a win
+            let kind = if share > pred.amount {
+                PayoutKind::Win
+            } else {
+                PayoutKind::Loss
+            };
+            payouts.push_back(Payout {
+                user: pred.user,
+                amount: share,
+                kind,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Claims pending winnings and adds to balance
+    /// Rounds past the claim deadline are left pending; fails with `ClaimExpired` if all of them are
+    pub fn claim_winnings(env: Env, user: Address) -> Result<i128, ContractError> {
+        user.require_auth();
+
+        Self::_claim_winnings(&env, user)
+    }
+
+    /// Claims pending winnings into balance, then places an Up/Down bet
+    /// Returns the amount claimed; expired winnings are left for the sweep and don't block
+    /// the bet. If the bet fails the claim is reverted with it
+    pub fn claim_and_bet(
+        env: Env,
+        user: Address,
+        amount: i128,
+        side: BetSide,
+    ) -> Result<i128, ContractError> {
+        user.require_auth();
+
+        let claimed = Self::_claim_unexpired(&env, user.clone())?;
+        Self::_place_updown_bet(&env, user, amount, side)?;
+
+        Ok(claimed)
+    }
+
+    /// Sets whether place_bet claims the user's pending winnings before betting
+    pub fn set_auto_claim(env: Env, user: Address, enabled: bool) -> Result<(), ContractError> {
+        user.require_auth();
+
+        let key = DataKey::AutoClaim(user.clone());
+        if enabled {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("autoclaim"), symbol_short!("updated")),
+            (user, enabled),
+        );
+
+        Ok(())
+    }
+
+    /// Returns true if place_bet claims the user's pending winnings first
+    pub fn get_auto_claim(env: Env, user: Address) -> bool {
+        env.storage().persistent().has(&DataKey::AutoClaim(user))
+    }
+
+    fn _claim_winnings(env: &Env, user: Address) -> Result<i128, ContractError> {
+        let pending = Self::get_pending_winnings(env.clone(), user.clone());
+        let claimed = Self::_claim_unexpired(env, user)?;
+        if claimed == 0 && pending > 0 {
+            return Err(ContractError::ClaimExpired);
+        }
+
+        Ok(claimed)
+    }
+
+    /// Claims the pending winnings of every round still inside the claim deadline
+    /// Expired rounds stay pending for sweep_unclaimed; returns the amount claimed
+    fn _claim_unexpired(env: &Env, user: Address) -> Result<i128, ContractError> {
+        let mut by_round = Self::_pending_rounds(env, user.clone());
+        // Winnings from before the per-round breakdown have no credit ledger, so never expire
+        let mut claimable = Self::_unattributed_pending(env, user.clone(), &by_round)?;
+        for (round_id, (amount, credited_at)) in by_round.clone().iter() {
+            if !Self::_is_claim_expired(env, credited_at) {
+                claimable = claimable
+                    .checked_add(amount)
+                    .ok_or(ContractError::Overflow)?;
+                by_round.remove(round_id);
+            }
+        }
+
+        if claimable == 0 {
+            return Ok(0);
+        }
+        Self::_pay_claim(env, user.clone(), claimable)?;
+        Self::_set_pending_rounds(env, user, &by_round);
+
+        Ok(claimable)
+    }
+
+    /// Claims only the pending winnings credited from round_id; returns the amount claimed
+    /// Fails with `ClaimExpired` once that round's claim deadline has passed
+    pub fn claim_round(env: Env, user: Address, round_id: u32) -> Result<i128, ContractError> {
+        user.require_auth();
+
+        let mut by_round = Self::_pending_rounds(&env, user.clone());
+        let (amount, credited_at) = match by_round.get(round_id) {
+            Some(entry) => entry,
+            None => return Ok(0),
+        };
+        if Self::_is_claim_expired(&env, credited_at) {
+            return Err(ContractError::ClaimExpired);
+        }
+
+        Self::_pay_claim(&env, user.clone(), amount)?;
+        by_round.remove(round_id);
+        Self::_set_pending_rounds(&env, user, &by_round);
+
+        Ok(amount)
+    }
+
+    /// Returns the user's pending winnings as (round id, amount), oldest round first
+    /// Winnings credited before the per-round breakdown existed only show in the total
+    pub fn get_pending_by_round(env: Env, user: Address) -> Vec<(u32, i128)> {
+        let mut pending = Vec::new(&env);
+        for (round_id, (amount, _)) in Self::_pending_rounds(&env, user).iter() {
+            pending.push_back((round_id, amount));
+        }
+        pending
+    }
+
+    /// The user's pending winnings per round id, with the ledger each round was first credited
+    fn _pending_rounds(env: &Env, user: Address) -> Map<u32, (i128, u32)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingByRound(user))
+            .unwrap_or(Map::new(env))
+    }
+
+    fn _set_pending_rounds(env: &Env, user: Address, by_round: &Map<u32, (i128, u32)>) {
+        let key = DataKey::PendingByRound(user);
+        if by_round.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, by_round);
+        }
+    }
+
+    /// Pending winnings credited before the per-round breakdown existed
+    fn _unattributed_pending(
+        env: &Env,
+        user: Address,
+        by_round: &Map<u32, (i128, u32)>,
+    ) -> Result<i128, ContractError> {
+        let mut unattributed = Self::get_pending_winnings(env.clone(), user);
+        for (_, (amount, _)) in by_round.iter() {
+            unattributed = unattributed
+                .checked_sub(amount)
+                .ok_or(ContractError::Overflow)?;
+        }
+        Ok(unattributed.max(0))
+    }
+
+    /// Moves amount out of the user's pending winnings into balance (and savings, per their split)
+    /// Callers check the claim deadline of the rounds being paid
+    fn _pay_claim(env: &Env, user: Address, amount: i128) -> Result<(), ContractError> {
+        let saved = amount
+            .checked_mul(Self::get_savings_split_bps(env.clone(), user.clone()) as i128)
+            .ok_or(ContractError::Overflow)?
+            / 10_000;
+        if saved > 0 {
+            Self::_adjust_savings(env, user.clone(), saved)?;
+        }
+
+        let current_balance = Self::balance(env.clone(), user.clone());
+        let new_balance = current_balance
+            .checked_add(amount - saved)
+            .ok_or(ContractError::Overflow)?;
+        Self::_set_balance(env, user.clone(), new_balance)?;
+
+        let claimed_key = DataKey::TotalClaimed(user.clone());
+        let total_claimed: i128 = env.storage().persistent().get(&claimed_key).unwrap_or(0);
+        let total_claimed = total_claimed
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        env.storage().persistent().set(&claimed_key, &total_claimed);
+
+        let key = DataKey::PendingWinnings(user.clone());
+        let remaining = Self::get_pending_winnings(env.clone(), user.clone())
+            .checked_sub(amount)
+            .ok_or(ContractError::Overflow)?;
+        if remaining == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &remaining);
+        }
+
+        Self::_adjust_pending_total(env, -amount)
+    }
+
+    /// Sets the share of each claim the user sets aside in savings instead of their balance
+    /// Savings can't be bet until moved back with withdraw_savings; 0 turns the split off
+    pub fn set_savings_split_bps(env: Env, user: Address, bps: u32) -> Result<(), ContractError> {
+        user.require_auth();
+
+        if bps > 10_000 {
+            return Err(ContractError::InvalidBasisPoints);
+        }
+
+        let key = DataKey::SavingsSplitBps(user.clone());
+        if bps == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &bps);
+        }
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("split")),
+            (user, bps),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the share of each claim routed to the user's savings (0 = none)
+    pub fn get_savings_split_bps(env: Env, user: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SavingsSplitBps(user))
+            .unwrap_or(0)
+    }
+
+    /// Returns the user's savings, which are not spendable on bets
+    pub fn get_savings(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Savings(user))
+            .unwrap_or(0)
+    }
+
+    /// Moves all of the user's savings into their spendable balance; returns the amount moved
+    pub fn withdraw_savings(env: Env, user: Address) -> Result<i128, ContractError> {
+        user.require_auth();
+
+        let saved = Self::get_savings(env.clone(), user.clone());
+        if saved == 0 {
+            return Ok(0);
+        }
+
+        let new_balance = Self::balance(env.clone(), user.clone())
+            .checked_add(saved)
+            .ok_or(ContractError::Overflow)?;
+        Self::_adjust_savings(&env, user.clone(), -saved)?;
+        Self::_set_balance(&env, user.clone(), new_balance)?;
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("withdrawn")),
+            (user, saved),
+        );
+
+        Ok(saved)
+    }
+
+    /// Adds delta to the user's savings and the savings total
+    fn _adjust_savings(env: &Env, user: Address, delta: i128) -> Result<(), ContractError> {
+        let key = DataKey::Savings(user.clone());
+        let saved = Self::get_savings(env.clone(), user)
+            .checked_add(delta)
+            .ok_or(ContractError::Overflow)?;
+        if saved == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &saved);
+        }
+
+        let total: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TotalSavings)
+            .unwrap_or(0);
+        let total = total.checked_add(delta).ok_or(ContractError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::TotalSavings, &total);
+
+        Ok(())
+    }
+
+    /// Returns the sum of all users' unclaimed pending winnings
+    pub fn get_pending_total(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingTotal)
+            .unwrap_or(0)
+    }
+
+    /// Returns the total winnings a user has ever claimed
+    pub fn get_total_claimed(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TotalClaimed(user))
+            .unwrap_or(0)
+    }
+
+    /// Sets how many ledgers after resolution winnings stay claimable (admin only)
+    /// ledgers: 0 disables the deadline
+    pub fn set_claim_deadline(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::ClaimDeadlineLedgers, &ledgers);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("claim_dl")),
+            ledgers,
+        );
+
+        Ok(())
+    }
+
+    /// Returns the claim deadline in ledgers (0 = no deadline)
+    pub fn get_claim_deadline(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ConfigKey::ClaimDeadlineLedgers)
+            .unwrap_or(0)
+    }
+
+    /// Moves expired pending winnings of the given users into the fee treasury (admin only)
+    /// Only rounds past their claim deadline are swept; returns the total amount swept
+    pub fn sweep_unclaimed(env: Env, users: Vec<Address>) -> Result<i128, ContractError> {
+        Self::_require_admin(&env)?;
+
+        let mut swept: i128 = 0;
+        for user in users.iter() {
+            let mut by_round = Self::_pending_rounds(&env, user.clone());
+            let mut expired: i128 = 0;
+            for (round_id, (amount, credited_at)) in by_round.clone().iter() {
+                if Self::_is_claim_expired(&env, credited_at) {
+                    expired = expired.checked_add(amount).ok_or(ContractError::Overflow)?;
+                    by_round.remove(round_id);
+                }
+            }
+            if expired == 0 {
+                continue;
+            }
+
+            let key = DataKey::PendingWinnings(user.clone());
+            let remaining = Self::get_pending_winnings(env.clone(), user.clone())
+                .checked_sub(expired)
+                .ok_or(ContractError::Overflow)?;
+            if remaining == 0 {
+                env.storage().persistent().remove(&key);
+            } else {
+                env.storage().persistent().set(&key, &remaining);
+            }
+            Self::_set_pending_rounds(&env, user, &by_round);
+            swept = swept.checked_add(expired).ok_or(ContractError::Overflow)?;
+        }
+
+        if swept > 0 {
+            Self::_adjust_pending_total(&env, -swept)?;
+            Self::_credit_treasury(&env, swept)?;
+
+            #[allow(deprecated)]
+            env.events()
+                .publish((symbol_short!("claims"), symbol_short!("swept")), swept);
+        }
+
+        Ok(swept)
+    }
+
+    /// Returns the vXLM held by the protocol fee treasury
+    pub fn get_fee_treasury(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::FeeTreasury)
+            .unwrap_or(0)
+    }
+
+    /// Moves `amount` vXLM from the fee treasury to `to`'s balance (admin only)
+    /// Returns what is left in the treasury
+    pub fn withdraw_fees_amount(
+        env: Env,
+        to: Address,
+        amount: i128,
+    ) -> Result<i128, ContractError> {
+        Self::_require_admin(&env)?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
+
+        let treasury = Self::get_fee_treasury(env.clone());
+        if treasury < amount {
+            return Err(ContractError::InsufficientBalance);
+        }
+
+        let treasury = treasury
+            .checked_sub(amount)
+            .ok_or(ContractError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::FeeTreasury, &treasury);
+
+        let balance = Self::balance(env.clone(), to.clone())
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        Self::_set_balance(&env, to.clone(), balance)?;
+
+        #[allow(deprecated)]
+        env.events().publish((symbol_short!("fees"), to), amount);
+
+        Ok(treasury)
+    }
+
+    /// Returns the built-in defaults: round windows, the mint_initial grant and oracle staleness
+    pub fn get_defaults(_env: Env) -> Defaults {
+        Defaults {
+            bet_window_ledgers: DEFAULT_BET_LEDGERS,
+            run_window_ledgers: DEFAULT_RUN_LEDGERS,
+            initial_mint: INITIAL_MINT,
+            oracle_max_age_secs: ORACLE_MAX_AGE_SECONDS,
+        }
+    }
+
+    /// Returns headline protocol numbers: rounds resolved, volume wagered, fees collected
+    pub fn get_protocol_stats(env: Env) -> ProtocolStats {
+        let storage = env.storage().persistent();
+        ProtocolStats {
+            total_rounds: storage.get(&DataKey::TotalRounds).unwrap_or(0),
+            total_volume: storage.get(&DataKey::TotalVolume).unwrap_or(0),
+            total_fees: storage.get(&DataKey::TotalFees).unwrap_or(0),
+        }
+    }
+
+    /// Returns the largest total stake of any resolved round
+    pub fn get_biggest_pot(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BiggestPot)
+            .unwrap_or(0)
+    }
+
+    /// Returns vXLM in existence: minted and unlocked grants minus burned
+    pub fn get_total_supply(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0)
+    }
+
+    /// Whether the vXLM in existence covers everything the contract owes
+    /// supply >= balances + stakes in the active round + pending winnings
+    /// + unsettled batched payouts + fee treasury + rollover pot + savings. Stakes lost with no winner
+    /// and rounding dust leave supply ahead, never behind
+    pub fn check_solvency(env: Env) -> bool {
+        let storage = env.storage().persistent();
+        let supply = Self::get_total_supply(env.clone());
+
+        let mut owed: Option<i128> = storage
+            .get::<_, i128>(&DataKey::TotalBalances)
+            .unwrap_or(0)
+            .checked_add(Self::get_pending_total(env.clone()))
+            .and_then(|owed| owed.checked_add(Self::get_fee_treasury(env.clone())))
+            .and_then(|owed| owed.checked_add(Self::get_rollover_pot(env.clone())))
+            .and_then(|owed| {
+                owed.checked_add(storage.get::<_, i128>(&DataKey::TotalSavings).unwrap_or(0))
+            });
+
+        // Stakes held for the active round
+        if storage.has(&DataKey::ActiveRound) {
+            let (up, down) = Self::get_side_totals(env.clone());
+            owed = owed
+                .and_then(|owed| owed.checked_add(up))
+                .and_then(|owed| owed.checked_add(down));
+        }
+
+        // Positions of a batched resolution not paid yet
+        if let Some(settlement) = storage.get::<_, Settlement>(&DataKey::Settlement) {
+            owed = owed.and_then(|owed| owed.checked_add(settlement.unpaid));
+        }
+
+        match owed {
+            Some(owed) => supply >= owed,
+            None => false,
+        }
+    }
+
+    /// Starts a new leaderboard season (admin only)
+    /// Earlier seasons' leaderboards stay readable
+    pub fn start_new_season(env: Env) -> Result<u32, ContractError> {
+        Self::_require_admin(&env)?;
+
+        let season = Self::get_season(env.clone())
+            .checked_add(1)
+            .ok_or(ContractError::Overflow)?;
+        env.storage().persistent().set(&DataKey::Season, &season);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("season"), symbol_short!("started")), season);
+
+        Ok(season)
+    }
+
+    /// Returns the current season number (starts at 0)
+    pub fn get_season(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Season)
+            .unwrap_or(0)
+    }
+
+    /// Returns a season's top players by wins, best first
+    pub fn get_leaderboard_for_season(env: Env, season: u32) -> Vec<LeaderboardEntry> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Leaderboard(season))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Adds to the fee treasury and the all-time fees counter
+    fn _credit_treasury(env: &Env, amount: i128) -> Result<(), ContractError> {
+        let treasury = Self::get_fee_treasury(env.clone())
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::FeeTreasury, &treasury);
+
+        let total_fees: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TotalFees)
+            .unwrap_or(0);
+        let total_fees = total_fees
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::TotalFees, &total_fees);
+
+        Ok(())
+    }
+
+    /// Adds a stake to the all-time wagered volume
+    fn _record_volume(env: &Env, amount: i128) -> Result<(), ContractError> {
+        let total_volume: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TotalVolume)
+            .unwrap_or(0);
+        let total_volume = total_volume
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::TotalVolume, &total_volume);
+
+        Ok(())
+    }
+
+    /// Adds to a user's pending winnings from round_id
+    /// The round's claim deadline runs from the first ledger it credited the user
+    fn _credit_pending(
+        env: &Env,
+        user: Address,
+        amount: i128,
+        round_id: u32,
+    ) -> Result<(), ContractError> {
+        let key = DataKey::PendingWinnings(user.clone());
+        let existing_pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        let new_pending = existing_pending
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        env.storage().persistent().set(&key, &new_pending);
+
+        let mut by_round = Self::_pending_rounds(env, user.clone());
+        let (from_round, credited_at) = by_round
+            .get(round_id)
+            .unwrap_or((0, env.ledger().sequence()));
+        let from_round = from_round
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        by_round.set(round_id, (from_round, credited_at));
+        Self::_set_pending_rounds(env, user, &by_round);
+
+        Self::_adjust_pending_total(env, amount)
+    }
+
+    /// Applies a change to the running total of outstanding pending winnings
+    fn _adjust_pending_total(env: &Env, delta: i128) -> Result<(), ContractError> {
+        let total = Self::get_pending_total(env.clone())
+            .checked_add(delta)
+            .ok_or(ContractError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingTotal, &total);
+
+        Ok(())
+    }
+
+    /// Whether winnings credited at credited_at are past the claim deadline
+    fn _is_claim_expired(env: &Env, credited_at: u32) -> bool {
+        let deadline = Self::get_claim_deadline(env.clone());
+        deadline != 0 && env.ledger().sequence() > credited_at.saturating_add(deadline)
+    }
+
+    /// Picks one of several equally close Precision guesses, weighted by stake
+    /// Seeded by the resolving ledger's sequence and the round id: deterministic
+    /// for a given ledger, not truly random
+    fn _draw_tie_winner(
+        env: &Env,
+        round: &Round,
+        tied: &Vec<PrecisionPrediction>,
+    ) -> PrecisionPrediction {
+        let mut seed = [0u8; 8];
+        seed[..4].copy_from_slice(&env.ledger().sequence().to_be_bytes());
+        seed[4..].copy_from_slice(&round.start_ledger.to_be_bytes());
+        let hash = env
+            .crypto()
+            .sha256(&Bytes::from_array(env, &seed))
+            .to_array();
+        let mut draw_bytes = [0u8; 16];
+        draw_bytes.copy_from_slice(&hash[..16]);
+        let draw = u128::from_be_bytes(draw_bytes);
+
+        let total_stake = tied.iter().fold(0u128, |total, pred| {
+            total.saturating_add(pred.amount as u128)
+        });
+        let mut point = draw % total_stake.max(1);
+
+        for pred in tied.iter() {
+            let stake = pred.amount as u128;
+            if point < stake {
+                return pred;
+            }
+            point -= stake;
+        }
+
+        // Unreachable while every stake is positive
+        tied.get_unchecked(0)
+    }
+
+    /// Distance of a Precision guess from the final price in the given mode
+    /// Percent distance is whole basis points, so guesses within the same bps tie
+    fn _precision_distance(mode: u32, predicted_price: u128, final_price: u128) -> u128 {
+        let diff = predicted_price.abs_diff(final_price);
+        if mode == PRECISION_DISTANCE_PERCENT && final_price > 0 {
+            diff.saturating_mul(10_000) / final_price
+        } else {
+            diff
+        }
+    }
+
+    /// Refunds every precision prediction its stake
+    fn _record_precision_refunds(
+        predictions: &Vec<PrecisionPrediction>,
+        payouts: &mut Vec<Payout>,
+    ) {
+        for pred in predictions.iter() {
+            payouts.push_back(Payout {
+                user: pred.user,
+                amount: pred.amount,
+                kind: PayoutKind::Refund,
+            });
+        }
+    }
+
+    /// Decodes a `Positions` entry written either by v1 (no `round_id`) or by this version
+    /// The host traps on a field mismatch, so the keys are checked before decoding
+    fn _decode_legacy_position(
+        env: &Env,
+        raw: Val,
+        round_id: u32,
+    ) -> Result<UserPosition, ContractError> {
+        let fields = Map::<Val, Val>::try_from_val(env, &raw)
+            .map_err(|_| ContractError::StateDecodeError)?;
+        let has_field = |name: &str| fields.contains_key(Symbol::new(env, name).to_val());
+        if !has_field("amount") || !has_field("side") {
+            return Err(ContractError::StateDecodeError);
+        }
+
+        match fields.len() {
+            2 => LegacyUserPosition::try_from_val(env, &raw)
+                .map(|legacy| UserPosition {
+                    amount: legacy.amount,
+                    side: legacy.side,
+                    round_id,
+                })
+                .map_err(|_| ContractError::StateDecodeError),
+            3 if has_field("round_id") => {
+                UserPosition::try_from_val(env, &raw).map_err(|_| ContractError::StateDecodeError)
+            }
+            _ => Err(ContractError::StateDecodeError),
+        }
+    }
+
+    /// Refunds positions left over from rounds before `round` and returns the rest
+    /// Their stakes sit in none of the round's pools, so they take no part in it
+    fn _split_stale_positions(
+        round: &Round,
+        positions: Map<Address, UserPosition>,
+        payouts: &mut Vec<Payout>,
+    ) -> Map<Address, UserPosition> {
+        let mut current = positions.clone();
+        for (user, position) in positions.iter() {
+            if position.round_id != round.start_ledger {
+                current.remove(user.clone());
+                payouts.push_back(Payout {
+                    user,
+                    amount: position.amount,
+                    kind: PayoutKind::Refund,
+                });
+            }
+        }
+        current
+    }
+
+    /// Records refunds when price unchanged
+    /// Each stake is drawn back out of its side's pool; a pool that would go
+    /// negative means the round's accounting is corrupt
+    fn _record_refunds(
+        round: &Round,
+        positions: Map<Address, UserPosition>,
+        payouts: &mut Vec<Payout>,
+    ) -> Result<(), ContractError> {
+        let mut remaining_up = round.pool_up;
+        let mut remaining_down = round.pool_down;
+        for (user, position) in positions.iter() {
+            let pool = match position.side {
+                BetSide::Up => &mut remaining_up,
+                BetSide::Down => &mut remaining_down,
+            };
+            *pool = pool
+                .checked_sub(position.amount)
+                .ok_or(ContractError::Overflow)?;
+            if *pool < 0 {
+                return Err(ContractError::PoolMismatch);
+            }
+
+            payouts.push_back(Payout {
+                user,
+                amount: position.amount,
+                kind: PayoutKind::Refund,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Records winnings for winning side
+    /// Losers first get the configured consolation back out of the losing pool,
+    /// then payout = bet + (bet / winning_pool) * remaining losing_pool.
+    /// Returns how much of the carried-over pot the winners take
+    fn _record_winnings(
+        env: &Env,
+        positions: Map<Address, UserPosition>,
+        payouts: &mut Vec<Payout>,
+        winning_side: BetSide,
+        winning_pool: i128,
+        losing_pool: i128,
+    ) -> Result<i128, ContractError> {
+        if winning_pool == 0 {
+            return Ok(0);
+        }
+
+        let consolation_bps = Self::get_consolation_bps(env.clone());
+        let (fee, carried, losing_pool) =
+            Self::_pool_for_winners(env, &positions, &winning_side, losing_pool)?;
+        if fee > 0 {
+            payouts.push_back(Payout {
+                user: env.current_contract_address(),
+                amount: fee,
+                kind: PayoutKind::Fee,
+            });
+        }
+
+        let shares =
+            Self::_winner_shares(env, &positions, &winning_side, winning_pool, losing_pool)?;
+
+        for (user, position) in positions.iter() {
+            if position.side == winning_side {
+                let share = shares.get(user.clone()).unwrap_or(0);
+                let payout = position
+                    .amount
+                    .checked_add(share)
+                    .ok_or(ContractError::Overflow)?;
+
+                payouts.push_back(Payout {
+                    user,
+                    amount: payout,
+                    kind: PayoutKind::Win,
+                });
+            } else {
+                payouts.push_back(Payout {
+                    user,
+                    amount: Self::_consolation_for(position.amount, consolation_bps)?,
+                    kind: PayoutKind::Loss,
+                });
+            }
+        }
+
+        Ok(carried)
+    }
+
+    /// Each winner's share of losing_pool, pro rata to stake and rounded per get_rounding
+    /// Rounding to nearest can overshoot by up to a stroop per winner; the excess is
+    /// taken back from the last winners so the shares never exceed losing_pool
+    fn _winner_shares(
+        env: &Env,
+        positions: &Map<Address, UserPosition>,
+        winning_side: &BetSide,
+        winning_pool: i128,
+        losing_pool: i128,
+    ) -> Result<Map<Address, i128>, ContractError> {
+        let nearest = Self::get_rounding(env.clone()) == ROUNDING_NEAREST;
+        let mut shares: Map<Address, i128> = Map::new(env);
+        let mut distributed: i128 = 0;
+
+        for (user, position) in positions.iter() {
+            if position.side != *winning_side {
+                continue;
+            }
+            let share = if nearest {
+                Self::_mul_div_nearest(env, position.amount, losing_pool, winning_pool)?
+            } else {
+                Self::_mul_div(env, position.amount, losing_pool, winning_pool)?
+            };
+            distributed = distributed
+                .checked_add(share)
+                .ok_or(ContractError::Overflow)?;
+            shares.set(user, share);
+        }
+
+        let mut excess = distributed
+            .checked_sub(losing_pool)
+            .ok_or(ContractError::Overflow)?
+            .max(0);
+        let winners = shares.keys();
+        for i in (0..winners.len()).rev() {
+            if excess == 0 {
+                break;
+            }
+            if let Some(user) = winners.get(i) {
+                let share = shares.get(user.clone()).unwrap_or(0);
+                let taken = share.min(excess);
+                shares.set(user, share - taken);
+                excess -= taken;
+            }
+        }
+
+        Ok(shares)
+    }
+
+    /// Splits the losing pool into (house fee, carried-over pot, what winners share)
+    /// The house takes its fee after consolation; any carried-over pot goes to winners in full.
+    /// Fails with `DeductionsExceedPool` rather than leave winners a negative share
+    fn _pool_for_winners(
+        env: &Env,
+        positions: &Map<Address, UserPosition>,
+        winning_side: &BetSide,
+        losing_pool: i128,
+    ) -> Result<(i128, i128, i128), ContractError> {
+        let losing_pool =
+            Self::_losing_pool_after_consolation(env, positions, winning_side, losing_pool)?;
+        if losing_pool < 0 {
+            return Err(ContractError::DeductionsExceedPool);
+        }
+
+        let fee = Self::_house_fee(env, losing_pool)?;
+        let remaining = losing_pool
+            .checked_sub(fee)
+            .ok_or(ContractError::Overflow)?;
+        if remaining < 0 {
+            return Err(ContractError::DeductionsExceedPool);
+        }
+
+        let carried = Self::get_rollover_pot(env.clone());
+        let pool = remaining
+            .checked_add(carried)
+            .ok_or(ContractError::Overflow)?;
+
+        Ok((fee, carried, pool))
+    }
+
+    /// What winners split: the losing pool minus every loser's consolation
+    fn _losing_pool_after_consolation(
+        env: &Env,
+        positions: &Map<Address, UserPosition>,
+        winning_side: &BetSide,
+        losing_pool: i128,
+    ) -> Result<i128, ContractError> {
+        let consolation_bps = Self::get_consolation_bps(env.clone());
+
+        let mut total_consolation: i128 = 0;
+        for position in positions.values().iter() {
+            if position.side != *winning_side {
+                total_consolation = total_consolation
+                    .checked_add(Self::_consolation_for(position.amount, consolation_bps)?)
+                    .ok_or(ContractError::Overflow)?;
+            }
+        }
+
+        losing_pool
+            .checked_sub(total_consolation)
+            .ok_or(ContractError::Overflow)
+    }
+
+    /// House fee on the part of the losing pool winners would otherwise split
+    fn _house_fee(env: &Env, losing_pool: i128) -> Result<i128, ContractError> {
+        Ok(losing_pool
+            .checked_mul(Self::get_fee_bps(env.clone()) as i128)
+            .ok_or(ContractError::Overflow)?
+            / 10_000)
+    }
+
+    /// Part of a losing stake returned as consolation
+    fn _consolation_for(amount: i128, consolation_bps: u32) -> Result<i128, ContractError> {
+        Ok(amount
+            .checked_mul(consolation_bps as i128)
+            .ok_or(ContractError::Overflow)?
+            / 10_000)
+    }
+
+    /// a * b / d for non-negative amounts, with a 256-bit intermediate so a large
+    /// product doesn't overflow when the quotient itself fits
+    fn _mul_div(env: &Env, a: i128, b: i128, d: i128) -> Result<i128, ContractError> {
+        let (a, b, d) = match (u128::try_from(a), u128::try_from(b), u128::try_from(d)) {
+            (Ok(a), Ok(b), Ok(d)) if d > 0 => (a, b, d),
+            _ => return Err(ContractError::Overflow),
+        };
+
+        U256::from_u128(env, a)
+            .mul(&U256::from_u128(env, b))
+            .div(&U256::from_u128(env, d))
+            .to_u128()
+            .and_then(|q| i128::try_from(q).ok())
+            .ok_or(ContractError::Overflow)
+    }
+
+    /// a * b / d rounded to the nearest integer (halves round up), otherwise as `_mul_div`
+    fn _mul_div_nearest(env: &Env, a: i128, b: i128, d: i128) -> Result<i128, ContractError> {
+        let (a, b, d) = match (u128::try_from(a), u128::try_from(b), u128::try_from(d)) {
+            (Ok(a), Ok(b), Ok(d)) if d > 0 => (a, b, d),
+            _ => return Err(ContractError::Overflow),
+        };
+
+        U256::from_u128(env, a)
+            .mul(&U256::from_u128(env, b))
+            .add(&U256::from_u128(env, d / 2))
+            .div(&U256::from_u128(env, d))
+            .to_u128()
+            .and_then(|q| i128::try_from(q).ok())
+            .ok_or(ContractError::Overflow)
+    }
+
+    /// Counts a win for the user; payout is the amount credited for it
+    pub(crate) fn _update_stats_win(env: &Env, user: Address, payout: i128) {
+        let key = DataKey::UserStats(user.clone());
+        let mut stats: UserStats = env.storage().persistent().get(&key).unwrap_or(UserStats {
+            total_wins: 0,
+            total_losses: 0,
+            current_streak: 0,
+            best_streak: 0,
+            biggest_win: 0,
+            total_staked: 0,
+            total_bets: 0,
+        });
+
+        stats.total_wins += 1;
+        stats.current_streak += 1;
+
+        if stats.current_streak > stats.best_streak {
+            stats.best_streak = stats.current_streak;
+        }
+
+        if payout > stats.biggest_win {
+            stats.biggest_win = payout;
+        }
+
+        env.storage().persistent().set(&key, &stats);
+
+        Self::_index_streak(env, user.clone(), stats.current_streak);
+        Self::_update_season_leaderboard(env, user);
+    }
+
+    /// Records the user's current streak in the streak index; 0 removes them
+    /// The index keeps the MAX_STREAK_RESULTS longest streaks, longest first
+    fn _index_streak(env: &Env, user: Address, streak: u32) {
+        let mut streaks: Vec<(Address, u32)> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Streaks)
+            .unwrap_or(Vec::new(env));
+        let existing = streaks.iter().position(|(indexed, _)| indexed == user);
+
+        if streak == 0 {
+            match existing {
+                Some(i) => streaks.remove(i as u32),
+                None => return,
+            };
+            env.storage().persistent().set(&DataKey::Streaks, &streaks);
+            return;
+        }
+
+        let mut index = match existing {
+            Some(i) => {
+                streaks.set(i as u32, (user, streak));
+                i as u32
+            }
+            None if streaks.len() < MAX_STREAK_RESULTS => {
+                streaks.push_back((user, streak));
+                streaks.len() - 1
+            }
+            None => {
+                // Full index: only displaces the shortest streak if strictly longer
+                let last = streaks.len() - 1;
+                match streaks.get(last) {
+                    Some((_, shortest)) if shortest < streak => streaks.set(last, (user, streak)),
+                    _ => return,
+                }
+                last
+            }
+        };
+
+        // Streaks only grow here, so the entry can only move up
+        while index > 0 {
+            let (above, current) = match (streaks.get(index - 1), streaks.get(index)) {
+                (Some(above), Some(current)) => (above, current),
+                _ => break,
+            };
+            if above.1 >= current.1 {
+                break;
+            }
+            streaks.set(index - 1, current);
+            streaks.set(index, above);
+            index -= 1;
+        }
+
+        env.storage().persistent().set(&DataKey::Streaks, &streaks);
+    }
+
+    /// Returns users whose current winning streak is above threshold, longest streak first
+    /// Only the MAX_STREAK_RESULTS longest streaks are indexed
+    pub fn get_streaks_above(env: Env, threshold: u32) -> Vec<(Address, u32)> {
+        let streaks: Vec<(Address, u32)> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Streaks)
+            .unwrap_or(Vec::new(&env));
+
+        let mut hot = Vec::new(&env);
+        for (user, streak) in streaks.iter() {
+            if streak <= threshold {
+                break;
+            }
+            hot.push_back((user, streak));
+        }
+        hot
+    }
+
+    /// Counts a win towards the current season and re-ranks its leaderboard
+    fn _update_season_leaderboard(env: &Env, user: Address) {
+        let season = Self::get_season(env.clone());
+
+        let wins_key = DataKey::SeasonWins(user.clone(), season);
+        let wins = env
+            .storage()
+            .persistent()
+            .get::<_, u32>(&wins_key)
+            .unwrap_or(0)
+            .saturating_add(1);
+        env.storage().persistent().set(&wins_key, &wins);
+
+        let board_key = DataKey::Leaderboard(season);
+        let mut board: Vec<LeaderboardEntry> = env
+            .storage()
+            .persistent()
+            .get(&board_key)
+            .unwrap_or(Vec::new(env));
+
+        let entry = LeaderboardEntry {
+            user: user.clone(),
+            wins,
+        };
+        let mut index = match board.iter().position(|e| e.user == user) {
+            Some(i) => {
+                board.set(i as u32, entry);
+                i as u32
+            }
+            None if board.len() < LEADERBOARD_SIZE => {
+                board.push_back(entry);
+                board.len() - 1
+            }
+            None => {
+                // Full board: only displaces the last place if strictly ahead
+                let last = board.len() - 1;
+                match board.get(last) {
+                    Some(e) if e.wins < wins => board.set(last, entry),
+                    _ => return,
+                }
+                last
+            }
+        };
+
+        // Move up past entries with fewer wins; ties keep whoever got there first
+        while index > 0 {
+            let (above, current) = match (board.get(index - 1), board.get(index)) {
+                (Some(above), Some(current)) => (above, current),
+                _ => break,
+            };
+            if above.wins >= current.wins {
+                break;
+            }
+            board.set(index - 1, current);
+            board.set(index, above);
+            index -= 1;
+        }
+
+        env.storage().persistent().set(&board_key, &board);
+    }
+
+    pub(crate) fn _update_stats_loss(env: &Env, user: Address) {
+        let key = DataKey::UserStats(user.clone());
+        let mut stats: UserStats = env.storage().persistent().get(&key).unwrap_or(UserStats {
+            total_wins: 0,
+            total_losses: 0,
+            current_streak: 0,
+            best_streak: 0,
+            biggest_win: 0,
+            total_staked: 0,
+            total_bets: 0,
+        });
+
+        stats.total_losses += 1;
+        let had_streak = stats.current_streak > 0;
+        stats.current_streak = 0;
+
+        env.storage().persistent().set(&key, &stats);
+
+        if had_streak {
+            Self::_index_streak(env, user, 0);
+        }
+    }
+
+    /// Grants vXLM that only becomes spendable once unlock_ledger is reached (admin only)
+    /// A second grant adds to the first and the later unlock ledger applies to both
+    pub fn mint_locked(
+        env: Env,
+        user: Address,
+        amount: i128,
+        unlock_ledger: u32,
+    ) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
+        if unlock_ledger <= env.ledger().sequence() {
+            return Err(ContractError::InvalidDuration);
+        }
+
+        let key = DataKey::Locked(user.clone());
+        let grant = match env.storage().persistent().get::<_, LockedGrant>(&key) {
+            Some(existing) => LockedGrant {
+                amount: existing
+                    .amount
+                    .checked_add(amount)
+                    .ok_or(ContractError::Overflow)?,
+                unlock_ledger: existing.unlock_ledger.max(unlock_ledger),
+            },
+            None => LockedGrant {
+                amount,
+                unlock_ledger,
+            },
+        };
+        env.storage().persistent().set(&key, &grant);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("locked"), user),
+            (amount, grant.unlock_ledger),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the user's locked grant, if any; it stays listed until their next bet after unlock
+    pub fn get_locked(env: Env, user: Address) -> Option<LockedGrant> {
+        env.storage().persistent().get(&DataKey::Locked(user))
+    }
+
+    /// Balance a user can bet with: spendable vXLM plus any grant that has unlocked
+    fn _unlocked_balance(env: &Env, user: &Address) -> i128 {
+        let balance = Self::balance(env.clone(), user.clone());
+        match Self::get_locked(env.clone(), user.clone()) {
+            Some(grant) if grant.unlock_ledger <= env.ledger().sequence() => {
+                balance.saturating_add(grant.amount)
+            }
+            _ => balance,
+        }
+    }
+
+    /// Moves an unlocked grant into the user's spendable balance
+    /// Grants count towards supply from this point, when they can first be spent
+    fn _release_unlocked(env: &Env, user: &Address) -> Result<(), ContractError> {
+        let key = DataKey::Locked(user.clone());
+        let grant: LockedGrant = match env.storage().persistent().get(&key) {
+            Some(grant) => grant,
+            None => return Ok(()),
+        };
+        if grant.unlock_ledger > env.ledger().sequence() {
+            return Ok(());
+        }
+
+        let balance = Self::balance(env.clone(), user.clone())
+            .checked_add(grant.amount)
+            .ok_or(ContractError::Overflow)?;
+        Self::_adjust_supply(env, grant.amount)?;
+        Self::_set_balance(env, user.clone(), balance)?;
+        env.storage().persistent().remove(&key);
+
+        Ok(())
+    }
+
+    /// Mints 1000 vXLM for new users (one-time only)
+    pub fn mint_initial(env: Env, user: Address) -> Result<i128, ContractError> {
+        user.require_auth();
+
+        Self::_check_not_blacklisted(&env, &user)?;
+
+        let key = DataKey::Balance(user.clone());
+
+        if let Some(existing_balance) = env.storage().persistent().get(&key) {
+            return Ok(existing_balance);
+        }
+
+        let initial_amount: i128 = INITIAL_MINT;
+        Self::_adjust_supply(&env, initial_amount)?;
+        Self::_set_balance(&env, user.clone(), initial_amount)?;
+
+        let total_users = Self::get_total_users(env.clone())
+            .checked_add(1)
+            .ok_or(ContractError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::TotalUsers, &total_users);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("mint"), user), initial_amount);
 
-                match min_diff {
-                    None => {
-                        // First prediction
-                        min_diff = Some(diff);
-                        winners.push_back(pred.clone());
-                    }
-                    Some(current_min) => {
-                        if diff < current_min {
-                            // New winner found, clear previous winners
-                            min_diff = Some(diff);
-                            winners = Vec::new(env);
-                            winners.push_back(pred.clone());
-                        } else if diff == current_min {
-                            // Tie - add to winners
-                            winners.push_back(pred.clone());
-                        }
-                    }
-                }
-            }
+        Ok(initial_amount)
+    }
+
+    /// Returns how many distinct users have received the mint_initial grant
+    pub fn get_total_users(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TotalUsers)
+            .unwrap_or(0)
+    }
+
+    /// Deposits the backing asset and mints the same amount of vXLM to `from`
+    /// Returns the new balance
+    pub fn deposit(env: Env, from: Address, amount: i128) -> Result<i128, ContractError> {
+        from.require_auth();
+
+        Self::_check_not_blacklisted(&env, &from)?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
         }
 
-        // Calculate total pot
-        let mut total_pot: i128 = 0;
-        for i in 0..predictions.len() {
-            if let Some(pred) = predictions.get(i) {
-                total_pot = total_pot
-                    .checked_add(pred.amount)
-                    .ok_or(ContractError::Overflow)?;
-            }
+        Collateral::pull(&env, &from, amount)?;
+        Self::_adjust_supply(&env, amount)?;
+
+        let balance = Self::balance(env.clone(), from.clone())
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        Self::_set_balance(&env, from.clone(), balance)?;
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("deposit"), from), amount);
+
+        Ok(balance)
+    }
+
+    /// Burns `amount` vXLM from `to` and returns the same amount of the backing asset
+    /// Returns the new balance
+    pub fn withdraw(env: Env, to: Address, amount: i128) -> Result<i128, ContractError> {
+        to.require_auth();
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
         }
 
-        // Distribute winnings to winner(s)
-        if !winners.is_empty() && total_pot > 0 {
-            let winner_count = winners.len() as i128;
-            let payout_per_winner = total_pot / winner_count;
-            let remainder = total_pot % winner_count;
+        let balance = Self::balance(env.clone(), to.clone());
+        if balance < amount {
+            return Err(ContractError::InsufficientBalance);
+        }
 
-            // Award to each winner
-            for i in 0..winners.len() {
-                if let Some(winner) = winners.get(i) {
-                    let key = DataKey::PendingWinnings(winner.user.clone());
-                    let existing_pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        Collateral::push(&env, &to, amount)?;
+        Self::_adjust_supply(&env, -amount)?;
 
-                    // First winner gets the remainder (if any)
-                    let payout = if i == 0 {
-                        payout_per_winner
-                            .checked_add(remainder)
-                            .ok_or(ContractError::Overflow)?
-                    } else {
-                        payout_per_winner
-                    };
+        let balance = balance.checked_sub(amount).ok_or(ContractError::Overflow)?;
+        Self::_set_balance(&env, to.clone(), balance)?;
 
-                    let new_pending = existing_pending
-                        .checked_add(payout)
-                        .ok_or(ContractError::Overflow)?;
-                    env.storage().persistent().set(&key, &new_pending);
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("withdraw"), to), amount);
 
-                    Self::_update_stats_win(env, winner.user.clone());
-                }
-            }
+        Ok(balance)
+    }
 
-            // Update stats for losers
-            for i in 0..predictions.len() {
-                if let Some(pred) = predictions.get(i) {
-                    let is_winner = winners.iter().any(|w| w.user == pred.user);
-                    if !is_winner {
-                        Self::_update_stats_loss(env, pred.user.clone());
-                    }
-                }
-            }
+    /// Returns the backing asset held for deposited vXLM
+    pub fn get_collateral(env: Env) -> i128 {
+        collateral::total(&env)
+    }
+
+    /// Returns user's vXLM balance
+    pub fn balance(env: Env, user: Address) -> i128 {
+        let key = DataKey::Balance(user);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Returns vXLM balances for multiple users, in the same order as `users`
+    pub fn balance_batch(env: Env, users: Vec<Address>) -> Vec<i128> {
+        let mut balances: Vec<i128> = Vec::new(&env);
+        for user in users.iter() {
+            balances.push_back(Self::balance(env.clone(), user));
+        }
+        balances
+    }
+
+    /// Rejects a bet that would push the round's pot above the configured cap
+    fn _check_pot_cap(env: &Env, pot: i128, amount: i128) -> Result<(), ContractError> {
+        let cap: i128 = env
+            .storage()
+            .persistent()
+            .get(&ConfigKey::MaxPot)
+            .unwrap_or(0);
+        if cap == 0 {
+            return Ok(());
+        }
+
+        let new_pot = pot.checked_add(amount).ok_or(ContractError::Overflow)?;
+        if new_pot > cap {
+            return Err(ContractError::PotCapExceeded);
         }
 
         Ok(())
     }
 
-    /// Claims pending winnings and adds to balance
-    pub fn claim_winnings(env: Env, user: Address) -> i128 {
-        user.require_auth();
-
-        let key = DataKey::PendingWinnings(user.clone());
-        let pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    /// Rejects a stake that would make up more than the configured share of the pot
+    /// The opening bet of a round is always allowed, as it is necessarily the whole pot
+    fn _check_user_share(env: &Env, pot: i128, amount: i128) -> Result<(), ContractError> {
+        let max_share_bps = Self::get_max_user_share_bps(env.clone());
+        if max_share_bps == 0 || pot == 0 {
+            return Ok(());
+        }
 
-        if pending == 0 {
-            return 0;
+        let new_pot = pot.checked_add(amount).ok_or(ContractError::Overflow)?;
+        let stake_bps = amount.checked_mul(10_000).ok_or(ContractError::Overflow)?;
+        let allowed_bps = new_pot
+            .checked_mul(max_share_bps as i128)
+            .ok_or(ContractError::Overflow)?;
+        if stake_bps > allowed_bps {
+            return Err(ContractError::UserShareTooLarge);
         }
 
-        let current_balance = Self::balance(env.clone(), user.clone());
-        let new_balance = current_balance + pending;
-        Self::_set_balance(&env, user.clone(), new_balance);
+        Ok(())
+    }
 
-        env.storage().persistent().remove(&key);
+    /// Adds a stake to the user's rolling volume window, rejecting it past the limit
+    fn _consume_daily_limit(env: &Env, user: Address, amount: i128) -> Result<(), ContractError> {
+        if let Some(window) = Self::_check_daily_limit(env, user.clone(), amount)? {
+            env.storage()
+                .persistent()
+                .set(&DataKey::VolumeWindow(user), &window);
+        }
 
-        pending
+        Ok(())
     }
 
-    /// Records refunds when price unchanged
-    fn _record_refunds(
+    /// Returns the user's volume window including this stake, or None if no limit is set
+    fn _check_daily_limit(
         env: &Env,
-        positions: Map<Address, UserPosition>,
-    ) -> Result<(), ContractError> {
-        let keys: Vec<Address> = positions.keys();
-
-        for i in 0..keys.len() {
-            if let Some(user) = keys.get(i) {
-                if let Some(position) = positions.get(user.clone()) {
-                    let key = DataKey::PendingWinnings(user.clone());
-                    let existing_pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
-                    let new_pending = existing_pending
-                        .checked_add(position.amount)
-                        .ok_or(ContractError::Overflow)?;
-                    env.storage().persistent().set(&key, &new_pending);
-                }
-            }
+        user: Address,
+        amount: i128,
+    ) -> Result<Option<(u32, i128)>, ContractError> {
+        let (limit, period) = Self::get_daily_limit(env.clone());
+        if limit == 0 {
+            return Ok(None);
+        }
+
+        let current_ledger = env.ledger().sequence();
+        let (mut window_start, mut used): (u32, i128) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VolumeWindow(user))
+            .unwrap_or((current_ledger, 0));
+
+        // Start a fresh window once the period has elapsed
+        if current_ledger >= window_start.saturating_add(period) {
+            window_start = current_ledger;
+            used = 0;
+        }
+
+        used = used.checked_add(amount).ok_or(ContractError::Overflow)?;
+        if used > limit {
+            return Err(ContractError::DailyLimitExceeded);
+        }
+
+        Ok(Some((window_start, used)))
+    }
+
+    fn _check_not_blacklisted(env: &Env, user: &Address) -> Result<(), ContractError> {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Blacklist(user.clone()))
+        {
+            return Err(ContractError::AddressBlacklisted);
         }
 
         Ok(())
     }
 
-    /// Records winnings for winning side
-    /// Formula: payout = bet + (bet / winning_pool) * losing_pool
-    fn _record_winnings(
-        env: &Env,
-        positions: Map<Address, UserPosition>,
-        winning_side: BetSide,
-        winning_pool: i128,
-        losing_pool: i128,
-    ) -> Result<(), ContractError> {
-        if winning_pool == 0 {
+    /// Rejects bets from the oracle address unless set_oracle_can_bet allows them
+    fn _check_not_oracle(env: &Env, user: &Address) -> Result<(), ContractError> {
+        if Self::get_oracle_can_bet(env.clone()) {
             return Ok(());
         }
+        if Self::get_oracle(env.clone()).is_some_and(|oracle| oracle == *user) {
+            return Err(ContractError::OracleCannotBet);
+        }
 
-        let keys: Vec<Address> = positions.keys();
+        Ok(())
+    }
 
-        for i in 0..keys.len() {
-            if let Some(user) = keys.get(i) {
-                if let Some(position) = positions.get(user.clone()) {
-                    if position.side == winning_side {
-                        let share_numerator = position
-                            .amount
-                            .checked_mul(losing_pool)
-                            .ok_or(ContractError::Overflow)?;
-                        let share = share_numerator / winning_pool;
-                        let payout = position
-                            .amount
-                            .checked_add(share)
-                            .ok_or(ContractError::Overflow)?;
+    /// Loads the admin address and requires its authorization
+    fn _require_admin(env: &Env) -> Result<Address, ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
 
-                        let key = DataKey::PendingWinnings(user.clone());
-                        let existing_pending: i128 =
-                            env.storage().persistent().get(&key).unwrap_or(0);
-                        let new_pending = existing_pending
-                            .checked_add(payout)
-                            .ok_or(ContractError::Overflow)?;
-                        env.storage().persistent().set(&key, &new_pending);
+        admin.require_auth();
 
-                        Self::_update_stats_win(env, user);
-                    } else {
-                        Self::_update_stats_loss(env, user);
-                    }
-                }
-            }
-        }
+        Ok(admin)
+    }
+
+    /// Writes the user's balance and keeps TotalBalances in step
+    pub(crate) fn _set_balance(
+        env: &Env,
+        user: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        let key = DataKey::Balance(user);
+        let previous: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+
+        let total_balances = env
+            .storage()
+            .persistent()
+            .get::<_, i128>(&DataKey::TotalBalances)
+            .unwrap_or(0)
+            .checked_sub(previous)
+            .and_then(|total| total.checked_add(amount))
+            .ok_or(ContractError::Overflow)?;
+
+        env.storage().persistent().set(&key, &amount);
+        env.storage()
+            .persistent()
+            .set(&DataKey::TotalBalances, &total_balances);
 
         Ok(())
     }
 
-    pub(crate) fn _update_stats_win(env: &Env, user: Address) {
-        let key = DataKey::UserStats(user);
-        let mut stats: UserStats = env.storage().persistent().get(&key).unwrap_or(UserStats {
-            total_wins: 0,
-            total_losses: 0,
-            current_streak: 0,
-            best_streak: 0,
-        });
+    /// Applies vXLM minted (positive) or burned (negative) to the total supply
+    fn _adjust_supply(env: &Env, delta: i128) -> Result<(), ContractError> {
+        let supply = Self::get_total_supply(env.clone())
+            .checked_add(delta)
+            .ok_or(ContractError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::TotalSupply, &supply);
 
-        stats.total_wins += 1;
-        stats.current_streak += 1;
+        Ok(())
+    }
+}
 
-        if stats.current_streak > stats.best_streak {
-            stats.best_streak = stats.current_streak;
+/// Admin powers only compiled into regulated deployments (`regulated` feature)
+#[cfg(feature = "regulated")]
+#[contractimpl]
+impl VirtualTokenContract {
+    /// Moves vXLM between two accounts under legal order (admin only)
+    /// Emits an audit event with both parties and the amount
+    pub fn admin_force_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        let admin = Self::_require_admin(&env)?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
         }
 
-        env.storage().persistent().set(&key, &stats);
-    }
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < amount {
+            return Err(ContractError::InsufficientBalance);
+        }
+        let from_balance = from_balance
+            .checked_sub(amount)
+            .ok_or(ContractError::Overflow)?;
+        Self::_set_balance(&env, from.clone(), from_balance)?;
 
-    pub(crate) fn _update_stats_loss(env: &Env, user: Address) {
-        let key = DataKey::UserStats(user);
-        let mut stats: UserStats = env.storage().persistent().get(&key).unwrap_or(UserStats {
-            total_wins: 0,
-            total_losses: 0,
-            current_streak: 0,
-            best_streak: 0,
-        });
+        let to_balance = Self::balance(env.clone(), to.clone())
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        Self::_set_balance(&env, to.clone(), to_balance)?;
 
-        stats.total_losses += 1;
-        stats.current_streak = 0;
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("admin"), symbol_short!("forced")),
+            (admin, from, to, amount),
+        );
 
-        env.storage().persistent().set(&key, &stats);
+        Ok(())
     }
 
-    /// Mints 1000 vXLM for new users (one-time only)
-    pub fn mint_initial(env: Env, user: Address) -> i128 {
-        user.require_auth();
+    /// Applies a signed correction to a user's balance after a dispute (admin only)
+    /// Minted or burned vXLM is reflected in the total supply; emits an audit event
+    pub fn adjust_balance(env: Env, user: Address, delta: i128) -> Result<i128, ContractError> {
+        let admin = Self::_require_admin(&env)?;
 
-        let key = DataKey::Balance(user.clone());
+        if delta == 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
 
-        if let Some(existing_balance) = env.storage().persistent().get(&key) {
-            return existing_balance;
+        let balance = Self::balance(env.clone(), user.clone())
+            .checked_add(delta)
+            .ok_or(ContractError::Overflow)?;
+        if balance < 0 {
+            return Err(ContractError::InsufficientBalance);
         }
+        Self::_adjust_supply(&env, delta)?;
+        Self::_set_balance(&env, user.clone(), balance)?;
 
-        let initial_amount: i128 = 1000_0000000;
-        env.storage().persistent().set(&key, &initial_amount);
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("admin"), symbol_short!("adjusted")),
+            (admin, user, delta),
+        );
 
-        initial_amount
+        Ok(balance)
     }
+}
 
-    /// Returns user's vXLM balance
-    pub fn balance(env: Env, user: Address) -> i128 {
-        let key = DataKey::Balance(user);
-        env.storage().persistent().get(&key).unwrap_or(0)
+/// Testnet-only maintenance, compiled out unless built with the `testnet` feature
+#[cfg(feature = "testnet")]
+#[contractimpl]
+impl VirtualTokenContract {
+    /// Returns roles, the active round, pending total and settings in one struct for debugging
+    pub fn dump_state(env: Env) -> StateDump {
+        let active_round = match env.storage().persistent().get(&DataKey::ActiveRound) {
+            Some(round) => RoundState::Active(round),
+            None => RoundState::None,
+        };
+        let (bet_window_ledgers, run_window_ledgers) = Self::_window_ledgers(&env);
+
+        StateDump {
+            admin: Self::get_admin(env.clone()),
+            oracle: Self::get_oracle(env.clone()),
+            active_round,
+            pending_total: Self::get_pending_total(env.clone()),
+            fee_treasury: Self::get_fee_treasury(env.clone()),
+            total_supply: Self::get_total_supply(env.clone()),
+            bet_window_ledgers,
+            run_window_ledgers,
+            default_mode: Self::get_default_mode(env.clone()),
+            max_pot: Self::get_max_pot(env.clone()),
+            fee_bps: Self::get_fee_bps(env.clone()),
+            consolation_bps: Self::get_consolation_bps(env.clone()),
+            draw_band_bps: Self::get_draw_band_bps(env.clone()),
+            max_user_share_bps: Self::get_max_user_share_bps(env.clone()),
+            min_participants: Self::get_min_participants_to_pay(env.clone()),
+            round_cooldown: Self::get_round_cooldown(env.clone()),
+            claim_deadline: Self::get_claim_deadline(env.clone()),
+            precision_payout_mode: Self::get_precision_payout_mode(env.clone()),
+            one_sided_policy: Self::get_one_sided_policy(env.clone()),
+            rounding: Self::get_rounding(env.clone()),
+            oracle_max_ledger_lag: Self::get_oracle_max_ledger_lag(env),
+        }
     }
 
-    pub(crate) fn _set_balance(env: &Env, user: Address, amount: i128) {
-        let key = DataKey::Balance(user);
-        env.storage().persistent().set(&key, &amount);
+    /// Wipes roles, the active round and all settings so `initialize` can run again (admin only)
+    /// Stakes in the active round are refunded to pending winnings first, as in cancel_round.
+    /// Balances, pending winnings, user history and the last round id are kept, so resolved
+    /// round ids are never handed out again
+    pub fn reset_contract(env: Env) -> Result<(), ContractError> {
+        Self::_require_admin(&env)?;
+
+        // Positions not paid yet would be dropped along with the settlement
+        if env.storage().persistent().has(&DataKey::Settlement) {
+            return Err(ContractError::SettlementInProgress);
+        }
+        if let Some(round) = env
+            .storage()
+            .persistent()
+            .get::<_, Round>(&DataKey::ActiveRound)
+        {
+            Self::_cancel_round(&env, &round)?;
+        }
+
+        let storage = env.storage().persistent();
+        for key in [
+            DataKey::Admin,
+            DataKey::Oracle,
+            DataKey::ActiveRound,
+            DataKey::Positions,
+            DataKey::UpDownPositions,
+            DataKey::PrecisionPositions,
+            DataKey::BetWindowLedgers,
+            DataKey::RunWindowLedgers,
+            DataKey::LastResolvedLedger,
+            DataKey::Settlement,
+            DataKey::SettlementPositions,
+            DataKey::ResolvedRound,
+            DataKey::ScheduledRound,
+        ] {
+            storage.remove(&key);
+        }
+        for key in [
+            ConfigKey::ClaimDeadlineLedgers,
+            ConfigKey::DrawBandBps,
+            ConfigKey::MaxPot,
+            ConfigKey::DailyLimit,
+            ConfigKey::RoundCooldown,
+            ConfigKey::MinParticipants,
+            ConfigKey::ConsolationBps,
+            ConfigKey::PrecisionPayoutMode,
+            ConfigKey::DefaultMode,
+            ConfigKey::MinBetLedgers,
+            ConfigKey::ResultRetention,
+            ConfigKey::MaxUserShareBps,
+            ConfigKey::FeeBps,
+            ConfigKey::OneSidedPolicy,
+            ConfigKey::PrecisionTolerance,
+            ConfigKey::PrecisionDistanceMode,
+            ConfigKey::OracleMaxLedgerLag,
+            ConfigKey::Rounding,
+            ConfigKey::HistoryLimit,
+            ConfigKey::PrecisionMaxPayout,
+            ConfigKey::PayoutCapPolicy,
+            ConfigKey::RequireFreshStartPrice,
+            ConfigKey::PrecisionZones,
+            ConfigKey::OracleCanBet,
+        ] {
+            storage.remove(&key);
+        }
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("admin"), symbol_short!("reset")), ());
+
+        Ok(())
     }
 }
@@ -47,4 +47,52 @@ pub enum ContractError {
     InvalidOracleRound = 19,
     /// An active round already exists and cannot be overwritten
     RoundAlreadyActive = 20,
+    /// Admin and oracle must be different addresses
+    AdminOracleConflict = 21,
+    /// Pending winnings are past the claim deadline
+    ClaimExpired = 22,
+    /// Basis point value exceeds 10_000 (100%)
+    InvalidBasisPoints = 23,
+    /// Bet would push the round's pot above the configured cap
+    PotCapExceeded = 24,
+    /// Not enough oracle checkpoints to compute a TWAP
+    NotEnoughCheckpoints = 25,
+    /// Bet would exceed the user's staking limit for the current period
+    DailyLimitExceeded = 26,
+    /// Not enough ledgers have passed since the last round was resolved
+    CooldownActive = 27,
+    /// The previous round's payouts have not all been settled yet
+    SettlementInProgress = 28,
+    /// Address has been blocked from minting and betting by the admin
+    AddressBlacklisted = 29,
+    /// Round pools don't match the sum of its positions
+    PoolMismatch = 30,
+    /// Oracle payload targets a round that was already resolved
+    AlreadyResolved = 31,
+    /// Bet would give one user more than the allowed share of the round's pot
+    UserShareTooLarge = 32,
+    /// Withdrawal exceeds the collateral backing deposited vXLM
+    InsufficientCollateral = 33,
+    /// Oracle resolved the round more ledgers after end_ledger than allowed
+    OracleTooLate = 34,
+    /// No round has been scheduled
+    NoScheduledRound = 35,
+    /// The scheduled round's start ledger has not been reached yet
+    TooEarlyToActivate = 36,
+    /// Consolation and fees together would take more than the losing pool
+    DeductionsExceedPool = 37,
+    /// Round history must keep at least one result
+    InvalidHistoryLimit = 38,
+    /// Stored state does not match the current schema and needs a migration
+    StateDecodeError = 39,
+    /// Start price is too far from the latest oracle checkpoint, or there is none
+    StartPriceStale = 40,
+    /// Zone payouts need price zones set with set_precision_zones
+    ZoneNotConfigured = 41,
+    /// A round already started at this ledger; round ids are never reused
+    RoundIdTaken = 42,
+    /// The oracle address may not bet unless set_oracle_can_bet allows it
+    OracleCannotBet = 43,
+    /// Oracle timestamp is ahead of the ledger's
+    OracleTimestampInFuture = 44,
 }
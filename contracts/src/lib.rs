@@ -10,6 +10,7 @@
 //! - Proportional payout distribution
 //! - Comprehensive error handling
 
+mod collateral;
 mod contract;
 mod errors;
 mod types;
@@ -19,4 +20,4 @@ mod tests;
 
 pub use contract::VirtualTokenContract;
 pub use errors::ContractError;
-pub use types::{BetSide, DataKey, Round, UserPosition, UserStats};
+pub use types::{BetSide, ConfigKey, DataKey, ProtocolStats, Round, UserPosition, UserStats};
@@ -2,10 +2,10 @@
 
 use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
 use crate::errors::ContractError;
-use crate::types::BetSide;
+use crate::types::{BetChoice, BetSide, DataKey, OraclePayload, UserPosition};
 use soroban_sdk::{
     testutils::{Address as _, Ledger as _},
-    Address, Env,
+    vec, Address, Env, IntoVal, InvokeError, Map, Symbol, Val,
 };
 
 #[test]
@@ -159,3 +159,728 @@ fn test_get_user_position_no_bet() {
     let position = client.get_user_position(&user);
     assert_eq!(position, None);
 }
+
+#[test]
+fn test_pot_cap_updown() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+
+    client.set_max_pot(&300_0000000);
+    assert_eq!(client.get_max_pot(), 300_0000000);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &200_0000000, &BetSide::Up);
+
+    // Exactly reaching the cap succeeds
+    client.place_bet(&bob, &100_0000000, &BetSide::Down);
+
+    // Any further stake is rejected and the balance is untouched
+    let result = client.try_place_bet(&charlie, &1, &BetSide::Up);
+    assert_eq!(result, Err(Ok(ContractError::PotCapExceeded)));
+    assert_eq!(client.balance(&charlie), 1000_0000000);
+}
+
+#[test]
+fn test_place_bet_partial_fills_up_to_cap() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+
+    client.set_max_pot(&300_0000000);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &200_0000000, &BetSide::Up);
+
+    // Only 100 of Bob's 250 fits under the cap
+    let accepted = client.place_bet_partial(&bob, &250_0000000, &BetSide::Down);
+    assert_eq!(accepted, 100_0000000);
+    assert_eq!(client.balance(&bob), 900_0000000);
+    assert_eq!(client.get_user_position(&bob).unwrap().amount, 100_0000000);
+    assert_eq!(client.get_active_round().unwrap().pool_down, 100_0000000);
+
+    // Pot is full: nothing is accepted
+    let result = client.try_place_bet_partial(&charlie, &50_0000000, &BetSide::Up);
+    assert_eq!(result, Err(Ok(ContractError::PotCapExceeded)));
+    assert_eq!(client.balance(&charlie), 1000_0000000);
+}
+
+#[test]
+fn test_place_bet_partial_without_cap_accepts_all() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+
+    client.create_round(&1_0000000, &None);
+    let accepted = client.place_bet_partial(&alice, &250_0000000, &BetSide::Up);
+    assert_eq!(accepted, 250_0000000);
+    assert_eq!(client.balance(&alice), 750_0000000);
+}
+
+#[test]
+fn test_pot_cap_precision() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.set_max_pot(&150_0000000);
+    client.create_round(&1_0000000, &Some(1));
+
+    client.place_precision_prediction(&alice, &150_0000000, &2297);
+
+    let result = client.try_place_precision_prediction(&bob, &10_0000000, &2300);
+    assert_eq!(result, Err(Ok(ContractError::PotCapExceeded)));
+}
+
+#[test]
+fn test_pot_cap_zero_is_unlimited() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&user);
+
+    client.set_max_pot(&0);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&user, &1000_0000000, &BetSide::Up);
+
+    // Negative caps are rejected
+    let result = client.try_set_max_pot(&-1);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBetAmount)));
+}
+
+#[test]
+fn test_daily_limit_resets_after_period() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&user);
+
+    // 150 vXLM per 100 ledgers
+    client.set_daily_limit(&150_0000000, &100);
+    assert_eq!(client.get_daily_limit(), (150_0000000, 100));
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&user, &100_0000000, &BetSide::Up);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Same window: only 50 vXLM of headroom left
+    client.create_round(&1_0000000, &None);
+    let result = client.try_place_bet(&user, &100_0000000, &BetSide::Up);
+    assert_eq!(result, Err(Ok(ContractError::DailyLimitExceeded)));
+    client.place_bet(&user, &50_0000000, &BetSide::Up);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 100;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+
+    // Window rolled over: full limit available again
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&user, &150_0000000, &BetSide::Down);
+}
+
+#[test]
+fn test_set_daily_limit_validation() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    let result = client.try_set_daily_limit(&-1, &100);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBetAmount)));
+
+    let result = client.try_set_daily_limit(&100_0000000, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidDuration)));
+
+    // Disabling needs no period
+    client.set_daily_limit(&0, &0);
+}
+
+#[test]
+fn test_can_place_bet_preconditions() {
+    let env = Env::default();
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 0;
+    });
+
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&user);
+    client.mint_initial(&other);
+
+    // No active round
+    let result = client.try_can_place_bet(&user, &100_0000000);
+    assert_eq!(result, Err(Ok(ContractError::NoActiveRound)));
+
+    client.create_round(&1_0000000, &None);
+
+    // Invalid amount
+    let result = client.try_can_place_bet(&user, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBetAmount)));
+
+    // Insufficient balance
+    let result = client.try_can_place_bet(&user, &2000_0000000);
+    assert_eq!(result, Err(Ok(ContractError::InsufficientBalance)));
+
+    // Over the staking limit
+    client.set_daily_limit(&50_0000000, &100);
+    let result = client.try_can_place_bet(&user, &100_0000000);
+    assert_eq!(result, Err(Ok(ContractError::DailyLimitExceeded)));
+    client.set_daily_limit(&0, &0);
+
+    // Over the pot cap
+    client.set_max_pot(&50_0000000);
+    let result = client.try_can_place_bet(&user, &100_0000000);
+    assert_eq!(result, Err(Ok(ContractError::PotCapExceeded)));
+    client.set_max_pot(&0);
+
+    // All good
+    client.can_place_bet(&user, &100_0000000);
+
+    // Already bet
+    client.place_bet(&user, &100_0000000, &BetSide::Up);
+    let result = client.try_can_place_bet(&user, &100_0000000);
+    assert_eq!(result, Err(Ok(ContractError::AlreadyBet)));
+
+    // Betting window closed
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 6;
+    });
+    let result = client.try_can_place_bet(&other, &100_0000000);
+    assert_eq!(result, Err(Ok(ContractError::RoundEnded)));
+}
+
+#[test]
+fn test_can_place_bet_wrong_mode() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&user);
+    client.create_round(&1_0000000, &Some(1));
+
+    let result = client.try_can_place_bet(&user, &100_0000000);
+    assert_eq!(result, Err(Ok(ContractError::WrongModeForPrediction)));
+}
+
+#[test]
+fn test_place_bet_replaces_stale_position() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&user);
+    client.create_round(&1_0000000, &None);
+
+    // Leftover position from an interrupted earlier round
+    env.as_contract(&contract_id, || {
+        let mut positions = Map::<Address, UserPosition>::new(&env);
+        positions.set(
+            user.clone(),
+            UserPosition {
+                amount: 40_0000000,
+                side: BetSide::Down,
+                round_id: 999,
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::UpDownPositions, &positions);
+    });
+
+    // Not treated as a duplicate bet; the stale stake goes back to pending
+    client.place_bet(&user, &100_0000000, &BetSide::Up);
+    assert_eq!(client.get_pending_winnings(&user), 40_0000000);
+
+    let position = client.get_user_position(&user).unwrap();
+    assert_eq!(position.amount, 100_0000000);
+    assert_eq!(position.side, BetSide::Up);
+    assert_eq!(position.round_id, 0);
+
+    // A second bet in the same round is still rejected
+    let result = client.try_place_bet(&user, &10_0000000, &BetSide::Up);
+    assert_eq!(result, Err(Ok(ContractError::AlreadyBet)));
+}
+
+#[test]
+fn test_resolve_refunds_stale_position_of_other_user() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&carol);
+    client.create_round(&1_0000000, &None);
+
+    // Bob's position from an interrupted earlier round is still stored
+    env.as_contract(&contract_id, || {
+        let mut positions = Map::<Address, UserPosition>::new(&env);
+        positions.set(
+            bob.clone(),
+            UserPosition {
+                amount: 40_0000000,
+                side: BetSide::Up,
+                round_id: 999,
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::UpDownPositions, &positions);
+    });
+
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&carol, &50_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Bob's stake comes back and takes no share of Carol's pool
+    assert_eq!(client.get_pending_winnings(&bob), 40_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 150_0000000);
+    assert_eq!(client.get_pending_winnings(&carol), 0);
+}
+
+#[test]
+fn test_get_side_totals_updown() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+
+    // No active round
+    assert_eq!(client.get_side_totals(), (0, 0));
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &30_0000000, &BetSide::Up);
+    client.place_bet(&carol, &70_0000000, &BetSide::Down);
+
+    assert_eq!(client.get_side_totals(), (130_0000000, 70_0000000));
+}
+
+#[test]
+fn test_get_largest_bets_per_side() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let dave = Address::generate(&env);
+    let erin = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+    client.mint_initial(&dave);
+    client.mint_initial(&erin);
+
+    // No active round
+    assert_eq!(client.get_largest_bets(), (0, 0));
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &40_0000000, &BetSide::Up);
+    client.place_bet(&bob, &250_0000000, &BetSide::Up);
+    client.place_bet(&carol, &15_0000000, &BetSide::Down);
+    client.place_bet(&dave, &90_0000000, &BetSide::Down);
+    client.place_bet(&erin, &60_0000000, &BetSide::Down);
+
+    assert_eq!(client.get_largest_bets(), (250_0000000, 90_0000000));
+}
+
+#[test]
+fn test_get_side_participant_counts() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+
+    // No active round
+    assert_eq!(client.get_side_participant_counts(), (0, 0));
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &40_0000000, &BetSide::Up);
+    client.place_bet(&bob, &250_0000000, &BetSide::Up);
+    client.place_bet(&carol, &15_0000000, &BetSide::Down);
+
+    assert_eq!(client.get_side_participant_counts(), (2, 1));
+}
+
+#[test]
+fn test_get_side_totals_precision() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&2297, &Some(1));
+    assert_eq!(client.get_side_totals(), (0, 0));
+
+    client.place_precision_prediction(&alice, &25_0000000, &2300);
+    client.place_precision_prediction(&bob, &40_0000000, &2250);
+
+    assert_eq!(client.get_side_totals(), (65_0000000, 0));
+}
+
+#[test]
+fn test_max_user_share_rejects_dominant_bet() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+
+    assert_eq!(
+        client.try_set_max_user_share_bps(&10_001),
+        Err(Ok(ContractError::InvalidBasisPoints))
+    );
+    client.set_max_user_share_bps(&5_000);
+    assert_eq!(client.get_max_user_share_bps(), 5_000);
+
+    client.create_round(&1_0000000, &None);
+
+    // The opening bet is the whole pot and always allowed
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+
+    // 500 into a pot of 100 would be over 80% of it
+    let result = client.try_place_bet(&bob, &500_0000000, &BetSide::Down);
+    assert_eq!(result, Err(Ok(ContractError::UserShareTooLarge)));
+    assert_eq!(client.balance(&bob), 1000_0000000);
+
+    // Matching the pot is exactly 50%
+    client.place_bet(&bob, &100_0000000, &BetSide::Down);
+    client.place_bet(&charlie, &50_0000000, &BetSide::Up);
+    assert_eq!(client.get_active_round().unwrap().pool_up, 150_0000000);
+}
+
+#[test]
+fn test_max_user_share_disabled_by_default() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    assert_eq!(client.get_max_user_share_bps(), 0);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &1_0000000, &BetSide::Up);
+    client.place_bet(&bob, &900_0000000, &BetSide::Down);
+    assert_eq!(client.get_active_round().unwrap().pool_down, 900_0000000);
+}
+
+#[test]
+fn test_locked_grant_only_bettable_after_unlock() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    assert_eq!(
+        client.try_mint_locked(&alice, &500_0000000, &0),
+        Err(Ok(ContractError::InvalidDuration))
+    );
+    client.mint_locked(&alice, &500_0000000, &20);
+    let grant = client.get_locked(&alice).unwrap();
+    assert_eq!(grant.amount, 500_0000000);
+    assert_eq!(grant.unlock_ledger, 20);
+
+    // Before unlock only the 1000 minted vXLM can be bet
+    client.create_round(&1_0000000, &None);
+    assert_eq!(
+        client.try_place_bet(&alice, &1200_0000000, &BetSide::Up),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+    assert_eq!(client.balance(&alice), 1000_0000000);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 20;
+    });
+    client.create_round(&1_0000000, &None);
+    client.can_place_bet(&alice, &1200_0000000);
+    client.place_bet(&alice, &1200_0000000, &BetSide::Up);
+
+    // The grant merged into the spendable balance
+    assert_eq!(client.balance(&alice), 300_0000000);
+    assert_eq!(client.get_locked(&alice), None);
+    assert!(client.check_solvency());
+}
+
+#[test]
+fn test_bet_side_round_trips_through_bet_and_resolution() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down);
+
+    // Each side reads back exactly as placed
+    assert_eq!(client.get_user_position(&alice).unwrap().side, BetSide::Up);
+    assert_eq!(client.get_user_position(&bob).unwrap().side, BetSide::Down);
+    assert_eq!(client.get_side_totals(), (100_0000000, 100_0000000));
+
+    // DOWN wins: only the Down position is paid
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert_eq!(client.get_pending_winnings(&bob), 200_0000000);
+    assert_eq!(
+        client.get_position_in_round(&alice, &0).unwrap().choice,
+        BetChoice::Side(BetSide::Up)
+    );
+    assert_eq!(
+        client.get_position_in_round(&bob, &0).unwrap().choice,
+        BetChoice::Side(BetSide::Down)
+    );
+}
+
+#[test]
+fn test_place_bet_rejects_malformed_side() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.create_round(&1_0000000, &None);
+
+    // Neither an unknown variant nor a bare integer decodes as a BetSide
+    let unknown_variant: Val = vec![&env, Symbol::new(&env, "Sideways")].into_val(&env);
+    for side in [unknown_variant, 0u32.into_val(&env)] {
+        let result = env.try_invoke_contract::<(), InvokeError>(
+            &contract_id,
+            &Symbol::new(&env, "place_bet"),
+            vec![
+                &env,
+                alice.into_val(&env),
+                100_0000000i128.into_val(&env),
+                side,
+            ],
+        );
+        assert!(result.is_err());
+    }
+
+    assert_eq!(client.get_user_position(&alice), None);
+    assert_eq!(client.balance(&alice), 1000_0000000);
+}
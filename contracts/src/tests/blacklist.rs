@@ -0,0 +1,87 @@
+//! Tests for the admin blacklist.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::errors::ContractError;
+use crate::types::{BetSide, OraclePayload};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    Address, Env,
+};
+
+#[test]
+fn test_blacklisted_user_cannot_bet_but_can_claim() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Alice wins a round before being blocked
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    client.set_blacklisted(&alice, &true);
+    assert!(client.is_blacklisted(&alice));
+    assert!(!client.is_blacklisted(&bob));
+
+    client.create_round(&1_0000000, &None);
+    let result = client.try_place_bet(&alice, &10_0000000, &BetSide::Up);
+    assert_eq!(result, Err(Ok(ContractError::AddressBlacklisted)));
+    assert_eq!(
+        client.try_can_place_bet(&alice, &10_0000000),
+        Err(Ok(ContractError::AddressBlacklisted))
+    );
+
+    // Other users are unaffected
+    client.place_bet(&bob, &10_0000000, &BetSide::Up);
+
+    // Already-earned winnings remain claimable
+    assert_eq!(client.claim_winnings(&alice), 200_0000000);
+    assert_eq!(client.balance(&alice), 1100_0000000);
+
+    // Unblocking restores betting
+    client.set_blacklisted(&alice, &false);
+    client.place_bet(&alice, &10_0000000, &BetSide::Up);
+}
+
+#[test]
+fn test_blacklisted_user_cannot_mint_or_predict() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.set_blacklisted(&user, &true);
+
+    assert_eq!(
+        client.try_mint_initial(&user),
+        Err(Ok(ContractError::AddressBlacklisted))
+    );
+
+    client.create_round(&2297, &Some(1));
+    let result = client.try_place_precision_prediction(&user, &10_0000000, &2300);
+    assert_eq!(result, Err(Ok(ContractError::AddressBlacklisted)));
+}
@@ -0,0 +1,534 @@
+//! Tests for oracle price checkpoints pushed during a round.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::errors::ContractError;
+use crate::types::{BetSide, OraclePayload};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    vec, Address, Env,
+};
+
+#[test]
+fn test_push_and_read_checkpoints() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+        li.sequence_number = 3;
+    });
+    client.push_checkpoint(&1_1000000, &990);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1030;
+        li.sequence_number = 9;
+    });
+    client.push_checkpoint(&1_2000000, &1030);
+
+    let checkpoints = client.get_checkpoints(&0);
+    assert_eq!(
+        checkpoints,
+        vec![&env, (1_1000000u128, 990u64), (1_2000000u128, 1030u64)]
+    );
+
+    // Unknown round has no checkpoints
+    assert_eq!(client.get_checkpoints(&99).len(), 0);
+}
+
+#[test]
+fn test_push_checkpoint_stale_timestamp() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+    });
+
+    // 400s old, past the 300s limit
+    let result = client.try_push_checkpoint(&1_1000000, &600);
+    assert_eq!(result, Err(Ok(ContractError::StaleOracleData)));
+    assert_eq!(client.get_checkpoints(&0).len(), 0);
+}
+
+#[test]
+fn test_push_checkpoint_without_round_sets_last_checkpoint() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+    });
+
+    client.push_checkpoint(&1_1000000, &900);
+    assert_eq!(client.get_last_checkpoint(), Some((1_1000000u128, 900u64)));
+    assert_eq!(client.get_checkpoints(&0).len(), 0);
+
+    // Still bound by freshness and time order
+    assert_eq!(
+        client.try_push_checkpoint(&1_1000000, &600),
+        Err(Ok(ContractError::StaleOracleData))
+    );
+    assert_eq!(
+        client.try_push_checkpoint(&1_1000000, &800),
+        Err(Ok(ContractError::StaleOracleData))
+    );
+}
+
+#[test]
+fn test_checkpoints_are_bounded() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 40;
+    });
+    for i in 1..=40u64 {
+        client.push_checkpoint(&(1_0000000 + i as u128), &i);
+    }
+
+    // Only the most recent 32 samples are kept
+    let checkpoints = client.get_checkpoints(&0);
+    assert_eq!(checkpoints.len(), 32);
+    assert_eq!(checkpoints.first().unwrap(), (1_0000009u128, 9u64));
+    assert_eq!(checkpoints.last().unwrap(), (1_0000040u128, 40u64));
+}
+
+#[test]
+fn test_push_checkpoint_fails_without_oracle_auth() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+
+    env.set_auths(&[]);
+
+    let result = client.try_push_checkpoint(&1_1000000, &0);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_push_checkpoint_out_of_order_rejected() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+    });
+    client.push_checkpoint(&1_1000000, &1000);
+
+    let result = client.try_push_checkpoint(&1_2000000, &990);
+    assert_eq!(result, Err(Ok(ContractError::StaleOracleData)));
+}
+
+#[test]
+fn test_resolve_round_twap() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // The round runs from 1000 to 1060
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+    });
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down);
+
+    // 1.2 for 30s, 1.3 for 20s, then a late dip to 0.95 for 10s
+    client.push_checkpoint(&1_2000000, &1000);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1030;
+    });
+    client.push_checkpoint(&1_3000000, &1030);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1050;
+    });
+    client.push_checkpoint(&9500000, &1050);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1060;
+        li.sequence_number = 12;
+    });
+
+    // (1.2 * 30 + 1.3 * 20 + 0.95 * 10) / 60 = 1.19166...
+    assert_eq!(client.get_twap(&0), Some(1_1916666));
+
+    client.resolve_round_twap();
+
+    // The spot dip is smoothed out: UP wins on the average
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 0);
+}
+
+#[test]
+fn test_resolve_round_twap_not_enough_checkpoints() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+    client.push_checkpoint(&1_1000000, &0);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+
+    assert_eq!(client.get_twap(&0), None);
+    let result = client.try_resolve_round_twap();
+    assert_eq!(result, Err(Ok(ContractError::NotEnoughCheckpoints)));
+}
+
+#[test]
+fn test_resolve_round_twap_before_end_fails() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+    client.push_checkpoint(&1_1000000, &0);
+    client.push_checkpoint(&1_2000000, &0);
+
+    let result = client.try_resolve_round_twap();
+    assert_eq!(result, Err(Ok(ContractError::RoundNotEnded)));
+}
+
+#[test]
+fn test_resolve_round_twap_stale_last_checkpoint() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 10;
+    });
+    client.push_checkpoint(&1_1000000, &0);
+    client.push_checkpoint(&1_2000000, &10);
+
+    // Oracle went quiet long before the round ended
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+        li.sequence_number = 12;
+    });
+
+    let result = client.try_resolve_round_twap();
+    assert_eq!(result, Err(Ok(ContractError::StaleOracleData)));
+}
+
+#[test]
+fn test_twap_only_weights_the_round_window() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    // The round runs from 1000 to 1060
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+    });
+    client.create_round(&1_0000000, &None);
+
+    // Sampled before the start, it counts from 1000 on
+    client.push_checkpoint(&1_1000000, &990);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1030;
+    });
+    client.push_checkpoint(&1_3000000, &1030);
+    // After the end: left out of the average
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1100;
+    });
+    client.push_checkpoint(&5000000, &1100);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1200;
+        li.sequence_number = 12;
+    });
+
+    // (1.1 * 30 + 1.3 * 30) / 60, the last sample weighted up to the round end, not now
+    assert_eq!(client.get_twap(&0), Some(1_2000000));
+    client.resolve_round_twap();
+    assert_eq!(
+        client
+            .get_round_results_page(&0, &1)
+            .get(0)
+            .unwrap()
+            .final_price,
+        1_2000000
+    );
+}
+
+#[test]
+fn test_push_checkpoint_rejects_future_timestamp() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+    });
+    let result = client.try_push_checkpoint(&1_1000000, &1001);
+    assert_eq!(result, Err(Ok(ContractError::OracleTimestampInFuture)));
+    client.push_checkpoint(&1_1000000, &1000);
+}
+
+#[test]
+fn test_resolve_from_last_checkpoint() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+    });
+    client.push_checkpoint(&1_2000000, &1000);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1050;
+    });
+    client.push_checkpoint(&9500000, &1050);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1060;
+        li.sequence_number = 12;
+    });
+    client.resolve_from_last_checkpoint();
+
+    // Settled at the latest sample, 0.95: DOWN wins
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert_eq!(client.get_pending_winnings(&bob), 200_0000000);
+}
+
+#[test]
+fn test_resolve_from_last_checkpoint_requires_checkpoint() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    assert_eq!(
+        client.try_resolve_from_last_checkpoint(),
+        Err(Ok(ContractError::NotEnoughCheckpoints))
+    );
+}
+
+#[test]
+fn test_resolve_from_last_checkpoint_stale() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+    });
+    client.push_checkpoint(&1_2000000, &1000);
+
+    // More than 300s after the last sample
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1301;
+        li.sequence_number = 12;
+    });
+    assert_eq!(
+        client.try_resolve_from_last_checkpoint(),
+        Err(Ok(ContractError::StaleOracleData))
+    );
+}
+
+#[test]
+fn test_fresh_start_price_checked_against_last_checkpoint() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.set_require_fresh_start_price(&true);
+    assert!(client.get_require_fresh_start_price());
+
+    // Nothing to compare against yet
+    assert_eq!(
+        client.try_create_round(&1_0000000, &None),
+        Err(Ok(ContractError::StartPriceStale))
+    );
+
+    // The oracle can push the reference price before any round is open
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+    });
+    client.push_checkpoint(&1_2000000, &1000);
+    assert_eq!(
+        client.try_create_round(&5_0000000, &None),
+        Err(Ok(ContractError::StartPriceStale))
+    );
+    assert!(client.get_active_round().is_none());
+
+    // A checkpoint past the oracle freshness window no longer counts
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1301;
+    });
+    assert_eq!(
+        client.try_create_round(&1_2000000, &None),
+        Err(Ok(ContractError::StartPriceStale))
+    );
+
+    // Within 1% of a fresh checkpoint
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1300;
+    });
+    client.create_round(&1_2100000, &None);
+    assert_eq!(client.get_active_round().unwrap().price_start, 1_2100000);
+}
+
+#[test]
+fn test_fresh_start_price_checked_when_scheduled_round_opens() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.push_checkpoint(&1_2000000, &env.ledger().timestamp());
+    client.resolve_round(&OraclePayload {
+        price: 1_2000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    client.set_require_fresh_start_price(&true);
+    client.schedule_round(&5_0000000, &None, &20);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 20;
+    });
+    assert_eq!(
+        client.try_activate_scheduled_round(),
+        Err(Ok(ContractError::StartPriceStale))
+    );
+    assert!(client.get_active_round().is_none());
+
+    // A start price within 1% of the checkpoint opens normally
+    client.schedule_round(&1_1900000, &None, &20);
+    client.activate_scheduled_round();
+    assert_eq!(client.get_active_round().unwrap().price_start, 1_1900000);
+}
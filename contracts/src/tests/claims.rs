@@ -0,0 +1,715 @@
+//! Tests for claiming winnings, claim deadlines and unclaimed sweeps.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::errors::ContractError;
+use crate::types::{BetSide, OraclePayload};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events as _, Ledger as _},
+    vec, Address, Env, IntoVal,
+};
+
+/// Runs one Up/Down round where `winner` bets UP and `loser` bets DOWN, resolved UP at ledger 12
+fn resolve_round_with_winner(
+    env: &Env,
+    client: &VirtualTokenContractClient,
+    winner: &Address,
+    loser: &Address,
+) {
+    client.create_round(&1_0000000, &None);
+    client.place_bet(winner, &100_0000000, &BetSide::Up);
+    client.place_bet(loser, &100_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+}
+
+#[test]
+fn test_claim_deadline_defaults_to_disabled() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    assert_eq!(client.get_claim_deadline(), 0);
+
+    resolve_round_with_winner(&env, &client, &alice, &bob);
+
+    // Without a deadline winnings never expire
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 1_000_000;
+    });
+    assert_eq!(client.claim_winnings(&alice), 200_0000000);
+}
+
+#[test]
+fn test_claim_just_before_deadline_succeeds() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_claim_deadline(&100);
+
+    // Resolved at ledger 12, claimable through ledger 112
+    resolve_round_with_winner(&env, &client, &alice, &bob);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 112;
+    });
+    assert_eq!(client.claim_winnings(&alice), 200_0000000);
+    assert_eq!(client.balance(&alice), 1100_0000000);
+}
+
+#[test]
+fn test_claim_after_deadline_fails_then_swept() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_claim_deadline(&100);
+
+    resolve_round_with_winner(&env, &client, &alice, &bob);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 113;
+    });
+    let result = client.try_claim_winnings(&alice);
+    assert_eq!(result, Err(Ok(ContractError::ClaimExpired)));
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+
+    // Admin sweeps the expired winnings into the treasury
+    let swept = client.sweep_unclaimed(&vec![&env, alice.clone(), bob.clone()]);
+    assert_eq!(swept, 200_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert_eq!(client.get_fee_treasury(), 200_0000000);
+
+    // Nothing left to claim or sweep
+    assert_eq!(client.claim_winnings(&alice), 0);
+    assert_eq!(client.sweep_unclaimed(&vec![&env, alice.clone()]), 0);
+}
+
+#[test]
+fn test_sweep_skips_unexpired_winnings() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_claim_deadline(&100);
+
+    resolve_round_with_winner(&env, &client, &alice, &bob);
+
+    let swept = client.sweep_unclaimed(&vec![&env, alice.clone()]);
+    assert_eq!(swept, 0);
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+    assert_eq!(client.get_fee_treasury(), 0);
+}
+
+#[test]
+fn test_claim_deadline_runs_per_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_claim_deadline(&100);
+
+    // Round 0 pays Alice 200 at ledger 12; round 90 pays her 100 at ledger 102
+    resolve_round_with_winner(&env, &client, &alice, &bob);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 90;
+    });
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &50_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 102;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 90,
+    });
+
+    // The later win doesn't extend round 0's deadline
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 150;
+    });
+    assert_eq!(
+        client.try_claim_round(&alice, &0),
+        Err(Ok(ContractError::ClaimExpired))
+    );
+    assert_eq!(client.claim_winnings(&alice), 100_0000000);
+    assert_eq!(
+        client.get_pending_by_round(&alice),
+        vec![&env, (0u32, 200_0000000i128)]
+    );
+
+    assert_eq!(
+        client.sweep_unclaimed(&vec![&env, alice.clone()]),
+        200_0000000
+    );
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert_eq!(client.get_fee_treasury(), 200_0000000);
+    assert!(client.check_solvency());
+}
+
+#[test]
+fn test_total_claimed_accumulates_across_claims() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    assert_eq!(client.get_total_claimed(&alice), 0);
+
+    resolve_round_with_winner(&env, &client, &alice, &bob);
+    assert_eq!(client.claim_winnings(&alice), 200_0000000);
+    assert_eq!(client.get_total_claimed(&alice), 200_0000000);
+
+    // Second round: Alice wins 50 from Bob's 50
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &50_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+
+    assert_eq!(client.claim_winnings(&alice), 100_0000000);
+    assert_eq!(client.get_total_claimed(&alice), 300_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert_eq!(client.get_total_claimed(&bob), 0);
+}
+
+#[test]
+fn test_pending_total_tracks_resolution_claims_and_sweeps() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+    client.set_claim_deadline(&100);
+    assert_eq!(client.get_pending_total(), 0);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Up);
+    client.place_bet(&carol, &150_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Alice 200 + Bob 100 outstanding
+    assert_eq!(client.get_pending_total(), 300_0000000);
+
+    client.claim_winnings(&alice);
+    assert_eq!(client.get_pending_total(), 100_0000000);
+
+    // Bob's winnings expire and are swept to the treasury
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 113;
+    });
+    client.sweep_unclaimed(&vec![&env, bob.clone()]);
+    assert_eq!(client.get_pending_total(), 0);
+}
+
+#[test]
+fn test_refunds_counted_in_pending_total() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Unchanged price: both stakes are refunded
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &40_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert_eq!(client.get_pending_total(), 140_0000000);
+    assert_eq!(
+        client.get_pending_total(),
+        client.get_pending_winnings(&alice) + client.get_pending_winnings(&bob)
+    );
+
+    client.claim_winnings(&bob);
+    assert_eq!(client.get_pending_total(), 100_0000000);
+    assert!(client.check_solvency());
+}
+
+#[test]
+fn test_refund_emits_event() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("refund"), alice.clone()).into_val(&env),
+                100_0000000i128.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("round"), symbol_short!("resolved")).into_val(&env),
+                1_0000000u128.into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_claim_and_bet_rolls_winnings_into_next_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    resolve_round_with_winner(&env, &client, &alice, &bob);
+    assert_eq!(client.balance(&alice), 900_0000000);
+
+    client.create_round(&1_5000000, &None);
+
+    // 900 + 200 claimed - 250 staked
+    assert_eq!(
+        client.claim_and_bet(&alice, &250_0000000, &BetSide::Down),
+        200_0000000
+    );
+    assert_eq!(client.balance(&alice), 850_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert_eq!(client.get_pending_total(), 0);
+
+    let position = client.get_user_position(&alice).unwrap();
+    assert_eq!(position.amount, 250_0000000);
+    assert_eq!(position.side, BetSide::Down);
+}
+
+#[test]
+fn test_claim_and_bet_without_round_keeps_winnings_pending() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    resolve_round_with_winner(&env, &client, &alice, &bob);
+
+    // No round to bet on: the claim is reverted with the bet
+    let result = client.try_claim_and_bet(&alice, &250_0000000, &BetSide::Up);
+    assert_eq!(result, Err(Ok(ContractError::NoActiveRound)));
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+    assert_eq!(client.balance(&alice), 900_0000000);
+    assert_eq!(client.get_total_claimed(&alice), 0);
+}
+
+#[test]
+fn test_claim_and_bet_with_expired_winnings_still_bets() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_claim_deadline(&100);
+
+    resolve_round_with_winner(&env, &client, &alice, &bob);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 113;
+    });
+    client.create_round(&1_5000000, &None);
+
+    // Nothing claimable: the expired 200 stays pending for the sweep
+    assert_eq!(client.claim_and_bet(&alice, &50_0000000, &BetSide::Up), 0);
+    assert_eq!(client.balance(&alice), 850_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+    assert_eq!(client.get_user_position(&alice).unwrap().amount, 50_0000000);
+
+    // Same for a user who never won anything
+    assert_eq!(client.claim_and_bet(&bob, &50_0000000, &BetSide::Down), 0);
+    assert_eq!(client.balance(&bob), 850_0000000);
+}
+
+#[test]
+fn test_place_bet_auto_claims_when_enabled() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    assert!(!client.get_auto_claim(&alice));
+    client.set_auto_claim(&alice, &true);
+    assert!(client.get_auto_claim(&alice));
+
+    resolve_round_with_winner(&env, &client, &alice, &bob);
+    assert_eq!(client.balance(&alice), 900_0000000);
+
+    client.create_round(&1_5000000, &None);
+
+    // 900 + 200 claimed - 250 staked
+    client.place_bet(&alice, &250_0000000, &BetSide::Down);
+    assert_eq!(client.balance(&alice), 850_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert_eq!(client.get_pending_total(), 0);
+    assert_eq!(client.get_total_claimed(&alice), 200_0000000);
+}
+
+#[test]
+fn test_place_bet_keeps_winnings_pending_without_auto_claim() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Turned on then off again: back to the default
+    client.set_auto_claim(&alice, &true);
+    client.set_auto_claim(&alice, &false);
+    assert!(!client.get_auto_claim(&alice));
+
+    resolve_round_with_winner(&env, &client, &alice, &bob);
+
+    client.create_round(&1_5000000, &None);
+    client.place_bet(&alice, &250_0000000, &BetSide::Down);
+    assert_eq!(client.balance(&alice), 650_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+    assert_eq!(client.get_total_claimed(&alice), 0);
+}
+
+#[test]
+fn test_claim_with_savings_split_sets_half_aside() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    assert_eq!(
+        client.try_set_savings_split_bps(&alice, &10_001),
+        Err(Ok(ContractError::InvalidBasisPoints))
+    );
+    client.set_savings_split_bps(&alice, &5_000);
+    assert_eq!(client.get_savings_split_bps(&alice), 5_000);
+
+    resolve_round_with_winner(&env, &client, &alice, &bob);
+
+    // 200 claimed: 100 to savings, 100 to the 900 balance
+    assert_eq!(client.claim_winnings(&alice), 200_0000000);
+    assert_eq!(client.get_savings(&alice), 100_0000000);
+    assert_eq!(client.balance(&alice), 1000_0000000);
+    assert!(client.check_solvency());
+
+    // Savings can't be staked
+    client.create_round(&1_5000000, &None);
+    assert_eq!(
+        client.try_place_bet(&alice, &1050_0000000, &BetSide::Up),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+
+    assert_eq!(client.withdraw_savings(&alice), 100_0000000);
+    assert_eq!(client.get_savings(&alice), 0);
+    assert_eq!(client.balance(&alice), 1100_0000000);
+    assert_eq!(client.withdraw_savings(&alice), 0);
+    assert!(client.check_solvency());
+}
+
+#[test]
+fn test_pending_by_round_and_claim_one_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Round 0 pays Alice 200; round 12 pays her 150
+    resolve_round_with_winner(&env, &client, &alice, &bob);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Down);
+    client.place_bet(&bob, &50_0000000, &BetSide::Up);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 9000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+
+    assert_eq!(
+        client.get_pending_by_round(&alice),
+        vec![&env, (0u32, 200_0000000i128), (12u32, 150_0000000i128)]
+    );
+    assert_eq!(client.get_pending_winnings(&alice), 350_0000000);
+
+    // Only round 12's winnings move to the balance
+    assert_eq!(client.claim_round(&alice, &12), 150_0000000);
+    assert_eq!(client.balance(&alice), 950_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+    assert_eq!(client.get_pending_total(), 200_0000000);
+    assert_eq!(
+        client.get_pending_by_round(&alice),
+        vec![&env, (0u32, 200_0000000i128)]
+    );
+    assert_eq!(client.claim_round(&alice, &12), 0);
+
+    // claim_winnings takes what is left and clears the breakdown
+    assert_eq!(client.claim_winnings(&alice), 200_0000000);
+    assert_eq!(client.get_pending_by_round(&alice).len(), 0);
+    assert_eq!(client.get_total_claimed(&alice), 350_0000000);
+    assert!(client.check_solvency());
+}
+
+#[test]
+fn test_withdraw_fees_amount_leaves_remainder() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let ops = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_fee_bps(&1000);
+
+    // 10% of Bob's 100 stake goes to the treasury
+    resolve_round_with_winner(&env, &client, &alice, &bob);
+    assert_eq!(client.get_fee_treasury(), 10_0000000);
+
+    assert_eq!(client.withdraw_fees_amount(&ops, &4_0000000), 6_0000000);
+    assert_eq!(client.get_fee_treasury(), 6_0000000);
+    assert_eq!(client.balance(&ops), 4_0000000);
+    assert!(client.check_solvency());
+}
+
+#[test]
+fn test_withdraw_fees_amount_over_request_fails() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let ops = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_fee_bps(&1000);
+
+    resolve_round_with_winner(&env, &client, &alice, &bob);
+
+    assert_eq!(
+        client.try_withdraw_fees_amount(&ops, &10_0000001),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+    assert_eq!(
+        client.try_withdraw_fees_amount(&ops, &0),
+        Err(Ok(ContractError::InvalidBetAmount))
+    );
+    assert_eq!(client.get_fee_treasury(), 10_0000000);
+    assert_eq!(client.balance(&ops), 0);
+
+    // The whole treasury can still be taken
+    assert_eq!(client.withdraw_fees_amount(&ops, &10_0000000), 0);
+}
@@ -0,0 +1,67 @@
+//! Tests for collateral-backed deposits and withdrawals.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::errors::ContractError;
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+#[test]
+fn test_deposit_and_withdraw_keep_collateral_in_sync() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    assert_eq!(client.get_collateral(), 0);
+
+    assert_eq!(client.deposit(&alice, &300_0000000), 300_0000000);
+    assert_eq!(client.deposit(&bob, &200_0000000), 200_0000000);
+    assert_eq!(client.get_collateral(), 500_0000000);
+
+    assert_eq!(client.withdraw(&alice, &120_0000000), 180_0000000);
+    assert_eq!(client.get_collateral(), 380_0000000);
+
+    // Deposited supply and collateral move together
+    assert_eq!(
+        client.balance(&alice) + client.balance(&bob),
+        client.get_collateral()
+    );
+
+    client.withdraw(&alice, &180_0000000);
+    client.withdraw(&bob, &200_0000000);
+    assert_eq!(client.get_collateral(), 0);
+    assert_eq!(client.balance(&alice), 0);
+}
+
+#[test]
+fn test_withdraw_rejects_invalid_amounts() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    assert_eq!(
+        client.try_deposit(&alice, &0),
+        Err(Ok(ContractError::InvalidBetAmount))
+    );
+
+    client.deposit(&alice, &100_0000000);
+    assert_eq!(
+        client.try_withdraw(&alice, &101_0000000),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+
+    // Free vXLM from mint_initial is not backed by collateral
+    client.mint_initial(&bob);
+    assert_eq!(
+        client.try_withdraw(&bob, &200_0000000),
+        Err(Ok(ContractError::InsufficientCollateral))
+    );
+    assert_eq!(client.balance(&bob), 1000_0000000);
+    assert_eq!(client.get_collateral(), 100_0000000);
+}
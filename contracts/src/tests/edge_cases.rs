@@ -1,6 +1,7 @@
 //! Tests for boundary conditions and unusual scenarios.
 
 use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::errors::ContractError;
 use crate::types::{BetSide, DataKey, OraclePayload, Round, UserPosition};
 use soroban_sdk::{
     testutils::{Address as _, Ledger as _},
@@ -85,7 +86,7 @@ fn test_round_with_only_one_side() {
 }
 
 #[test]
-fn test_accumulate_pending_winnings() {
+fn test_one_sided_refund_policy_is_default() {
     let env = Env::default();
     let contract_id = env.register(VirtualTokenContract, ());
     let client = VirtualTokenContractClient::new(&env, &contract_id);
@@ -99,35 +100,143 @@ fn test_accumulate_pending_winnings() {
     client.initialize(&admin, &oracle);
     client.mint_initial(&alice);
 
-    // Round 1: Alice bets UP and wins
+    assert_eq!(client.get_one_sided_policy(), 0);
+    assert_eq!(
+        client.try_set_one_sided_policy(&3),
+        Err(Ok(ContractError::InvalidMode))
+    );
+
+    // Explicitly selecting the default behaves like test_round_with_only_one_side
+    client.set_one_sided_policy(&0);
     client.create_round(&1_0000000, &None);
     client.place_bet(&alice, &100_0000000, &BetSide::Up);
 
-    env.as_contract(&contract_id, || {
-        let mut positions = Map::<Address, UserPosition>::new(&env);
-        positions.set(
-            alice.clone(),
-            UserPosition {
-                amount: 100_0000000,
-                side: BetSide::Up,
-            },
-        );
-        env.storage()
-            .persistent()
-            .set(&DataKey::Positions, &positions);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
 
-        let mut round: Round = env
-            .storage()
-            .persistent()
-            .get(&DataKey::ActiveRound)
-            .unwrap();
-        round.pool_up = 100_0000000;
-        round.pool_down = 50_0000000;
-        env.storage()
-            .persistent()
-            .set(&DataKey::ActiveRound, &round);
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+    assert_eq!(client.get_rollover_pot(), 0);
+}
+
+#[test]
+fn test_one_sided_rollover_pays_next_winners() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_one_sided_policy(&2);
+
+    // Only Up has stakes: the whole pot rolls over, nobody wins or loses
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Up);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert_eq!(client.get_rollover_pot(), 150_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert_eq!(client.get_pending_winnings(&bob), 0);
+    assert_eq!(client.get_user_stats(&alice).total_wins, 0);
+    assert!(client.check_solvency());
+
+    // Next two-sided round: the winner also takes the carried pot
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+
+    assert_eq!(client.get_pending_winnings(&alice), 300_0000000);
+    assert_eq!(client.get_rollover_pot(), 0);
+    assert!(client.check_solvency());
+}
+
+#[test]
+fn test_one_sided_void_refunds_minus_fee() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.set_fee_bps(&200);
+    client.set_one_sided_policy(&1);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
     });
 
+    assert_eq!(client.get_pending_winnings(&alice), 98_0000000);
+    assert_eq!(client.get_fee_treasury(), 2_0000000);
+    assert_eq!(client.get_user_stats(&alice).total_losses, 0);
+}
+
+#[test]
+fn test_accumulate_pending_winnings() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Round 1: Alice bets UP and wins against Bob
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
+
     // Advance ledger to allow resolution
     env.ledger().with_mut(|li| {
         li.sequence_number = 12;
@@ -166,3 +275,184 @@ fn test_accumulate_pending_winnings() {
     assert_eq!(claimed, total_pending);
     assert_eq!(client.get_pending_winnings(&alice), 0);
 }
+
+#[test]
+fn test_resolve_rejects_pools_not_matching_positions() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+
+    // Corrupt storage: Bob's position exists but was never added to the pool
+    env.as_contract(&contract_id, || {
+        let mut positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UpDownPositions)
+            .unwrap();
+        positions.set(
+            bob.clone(),
+            UserPosition {
+                amount: 50_0000000,
+                side: BetSide::Down,
+                round_id: 0,
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::UpDownPositions, &positions);
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .unwrap();
+        assert_eq!(round.pool_down, 0);
+    });
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    let result = client.try_resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(result, Err(Ok(ContractError::PoolMismatch)));
+
+    // Nothing was paid out and the round is still open
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert!(client.get_active_round().is_some());
+}
+
+#[test]
+fn test_refund_with_inconsistent_pool_fails_cleanly() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
+
+    // Corrupt storage: the Down pool holds less than Bob staked
+    env.as_contract(&contract_id, || {
+        let mut round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .unwrap();
+        round.pool_down = 20_0000000;
+        env.storage()
+            .persistent()
+            .set(&DataKey::ActiveRound, &round);
+    });
+
+    // Unchanged price takes the refund path
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    let result = client.try_resolve_round(&OraclePayload {
+        price: 1_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(result, Err(Ok(ContractError::PoolMismatch)));
+
+    // Nothing was refunded and the round is still there to investigate
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert_eq!(client.get_pending_winnings(&bob), 0);
+    assert!(client.get_active_round().is_some());
+}
+
+#[test]
+fn test_large_pools_share_does_not_overflow() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    // stake * losing_pool = 10^39 overflows i128, but the share (10^19) fits
+    let alice_stake: i128 = 100_000_000_000_000_000_000; // 10^20
+    let bob_stake: i128 = 10_000_000_000_000_000_000; // 10^19
+    env.as_contract(&contract_id, || {
+        VirtualTokenContract::_set_balance(&env, alice.clone(), alice_stake).unwrap();
+        VirtualTokenContract::_set_balance(&env, bob.clone(), bob_stake).unwrap();
+    });
+    assert!(alice_stake.checked_mul(bob_stake).is_none());
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &alice_stake, &BetSide::Up);
+    client.place_bet(&bob, &bob_stake, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert_eq!(client.get_pending_winnings(&alice), alice_stake + bob_stake);
+}
+
+#[test]
+fn test_set_balance_overflowing_total_fails() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    env.as_contract(&contract_id, || {
+        VirtualTokenContract::_set_balance(&env, alice.clone(), i128::MAX).unwrap();
+
+        // The running total can't hold another stroop: nothing is written
+        assert_eq!(
+            VirtualTokenContract::_set_balance(&env, bob.clone(), 1),
+            Err(ContractError::Overflow)
+        );
+    });
+    assert_eq!(client.balance(&bob), 0);
+    assert_eq!(client.balance(&alice), i128::MAX);
+}
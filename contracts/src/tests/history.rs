@@ -0,0 +1,403 @@
+//! Tests for per-user betting history and the archive of resolved rounds.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::errors::ContractError;
+use crate::types::{BetChoice, BetRecord, BetSide, DataKey, OraclePayload, RoundMode};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    vec, Address, Env,
+};
+
+#[test]
+fn test_get_position_in_round_across_resolved_rounds() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Round 0: Up/Down, only Alice bets
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Round 12: Precision, both predict
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &25_0000000, &2300);
+    client.place_precision_prediction(&bob, &40_0000000, &2250);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2290,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+
+    assert_eq!(
+        client.get_position_in_round(&alice, &0),
+        Some(BetRecord {
+            round_id: 0,
+            amount: 100_0000000,
+            choice: BetChoice::Side(BetSide::Up),
+        })
+    );
+    assert_eq!(
+        client.get_position_in_round(&alice, &12),
+        Some(BetRecord {
+            round_id: 12,
+            amount: 25_0000000,
+            choice: BetChoice::Price(2300),
+        })
+    );
+
+    // Bob skipped round 0
+    assert_eq!(client.get_position_in_round(&bob, &0), None);
+    assert_eq!(
+        client.get_position_in_round(&bob, &12).unwrap().amount,
+        40_0000000
+    );
+}
+
+#[test]
+fn test_get_user_rounds_lists_participated_rounds() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    assert_eq!(client.get_user_rounds(&alice), vec![&env]);
+
+    // Rounds 0, 12 and 24; Bob sits out round 12
+    for round_id in [0u32, 12, 24] {
+        client.create_round(&1_0000000, &None);
+        client.place_bet(&alice, &10_0000000, &BetSide::Up);
+        if round_id != 12 {
+            client.place_bet(&bob, &10_0000000, &BetSide::Down);
+        }
+
+        env.ledger().with_mut(|li| {
+            li.sequence_number = round_id + 12;
+        });
+        client.resolve_round(&OraclePayload {
+            price: 1_0000000,
+            timestamp: env.ledger().timestamp(),
+            round_id,
+        });
+    }
+
+    assert_eq!(client.get_user_rounds(&alice), vec![&env, 0, 12, 24]);
+    assert_eq!(client.get_user_rounds(&bob), vec![&env, 0, 24]);
+}
+
+#[test]
+fn test_get_user_rounds_drops_oldest_past_cap() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+
+    // 51 rounds, all refunded (price unchanged) so the balance never runs out
+    for i in 0..51u32 {
+        let round_id = i * 12;
+        client.create_round(&1_0000000, &None);
+        client.place_bet(&alice, &1_0000000, &BetSide::Up);
+
+        env.ledger().with_mut(|li| {
+            li.sequence_number = round_id + 12;
+        });
+        client.resolve_round(&OraclePayload {
+            price: 1_0000000,
+            timestamp: env.ledger().timestamp(),
+            round_id,
+        });
+        client.claim_winnings(&alice);
+    }
+
+    let rounds = client.get_user_rounds(&alice);
+    assert_eq!(rounds.len(), 50);
+    assert_eq!(rounds.first(), Some(12));
+    assert_eq!(rounds.last(), Some(600));
+}
+
+#[test]
+fn test_user_lifetime_volume_and_average_stake() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+
+    assert_eq!(client.get_user_lifetime_volume(&alice), 0);
+    assert_eq!(client.get_user_avg_stake(&alice), 0);
+
+    // Two Up/Down bets and a Precision prediction, each in its own round
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &10_0000000, &BetSide::Up);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &25_0000000, &BetSide::Down);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &55_0000000, &2300);
+
+    assert_eq!(client.get_user_lifetime_volume(&alice), 90_0000000);
+    assert_eq!(client.get_user_avg_stake(&alice), 30_0000000);
+
+    let stats = client.get_user_stats(&alice);
+    assert_eq!(stats.total_bets, 3);
+    assert_eq!(stats.total_staked, 90_0000000);
+}
+
+#[test]
+fn test_round_results_paged_in_resolution_order() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    // Three rounds, each created when the previous one resolves
+    let final_prices: [u128; 3] = [1_1000000, 9000000, 1_2000000];
+    for (i, final_price) in final_prices.iter().enumerate() {
+        let start_ledger = i as u32 * 12;
+        client.create_round(&1_0000000, &None);
+        env.ledger().with_mut(|li| {
+            li.sequence_number = start_ledger + 12;
+        });
+        client.resolve_round(&OraclePayload {
+            price: *final_price,
+            timestamp: env.ledger().timestamp(),
+            round_id: start_ledger,
+        });
+    }
+
+    let first_page = client.get_round_results_page(&0, &2);
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(first_page.get(0).unwrap().round_id, 0);
+    assert_eq!(first_page.get(0).unwrap().final_price, 1_1000000);
+    assert_eq!(first_page.get(0).unwrap().mode, RoundMode::UpDown);
+    assert_eq!(first_page.get(1).unwrap().round_id, 12);
+    assert_eq!(first_page.get(1).unwrap().resolved_ledger, 24);
+
+    let second_page = client.get_round_results_page(&2, &2);
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page.get(0).unwrap().round_id, 24);
+    assert_eq!(second_page.get(0).unwrap().final_price, 1_2000000);
+
+    assert_eq!(client.get_round_results_page(&3, &2).len(), 0);
+
+    // A missing result is skipped rather than failing the page
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().remove(&DataKey::RoundResult(1));
+    });
+    let page = client.get_round_results_page(&0, &u32::MAX);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(1).unwrap().round_id, 24);
+}
+
+#[test]
+fn test_history_limit_prunes_oldest_results() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    assert_eq!(client.get_history_limit(), 1000);
+    assert_eq!(
+        client.try_set_history_limit(&0),
+        Err(Ok(ContractError::InvalidHistoryLimit))
+    );
+    client.set_history_limit(&2);
+    assert_eq!(client.get_history_limit(), 2);
+
+    for i in 0..4u32 {
+        let start_ledger = i * 12;
+        client.create_round(&1_0000000, &None);
+        env.ledger().with_mut(|li| {
+            li.sequence_number = start_ledger + 12;
+        });
+        client.resolve_round(&OraclePayload {
+            price: 1_1000000,
+            timestamp: env.ledger().timestamp(),
+            round_id: start_ledger,
+        });
+    }
+
+    // Only results 2 and 3 remain
+    let page = client.get_round_results_page(&0, &50);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().round_id, 24);
+    assert_eq!(page.get(1).unwrap().round_id, 36);
+    assert_eq!(client.get_round_results_page(&0, &2).len(), 0);
+    assert!(client.get_last_result_delta().is_some());
+}
+
+#[test]
+fn test_last_result_delta_after_up_resolution() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    assert_eq!(client.get_last_result_delta(), None);
+
+    client.create_round(&2_0000000, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2_2500000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // +0.25 on 2.0 is +12.5%
+    assert_eq!(client.get_last_result_delta(), Some((2500000, 1_250)));
+
+    client.create_round(&2_0000000, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_9000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+
+    // Only the latest result counts
+    assert_eq!(client.get_last_result_delta(), Some((-1000000, -500)));
+}
+
+#[test]
+fn test_biggest_pot_keeps_largest_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    assert_eq!(client.get_biggest_pot(), 0);
+
+    // Round 1: a 300 vXLM Up/Down pot
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &200_0000000, &BetSide::Up);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down);
+    // Still open: not counted yet
+    assert_eq!(client.get_biggest_pot(), 0);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_biggest_pot(), 300_0000000);
+
+    // Round 2: a smaller 50 vXLM Precision pot
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &20_0000000, &2300);
+    client.place_precision_prediction(&bob, &30_0000000, &2250);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2290,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+    assert_eq!(client.get_biggest_pot(), 300_0000000);
+
+    // Round 3: a 400 vXLM Precision pot takes over
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &250_0000000, &2300);
+    client.place_precision_prediction(&bob, &150_0000000, &2250);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 36;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2290,
+        timestamp: env.ledger().timestamp(),
+        round_id: 24,
+    });
+    assert_eq!(client.get_biggest_pot(), 400_0000000);
+}
@@ -1,8 +1,15 @@
 //! Tests for contract initialization and token minting.
 
-use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::contract::{
+    VirtualTokenContract, VirtualTokenContractClient, DEFAULT_BET_LEDGERS, DEFAULT_RUN_LEDGERS,
+    INITIAL_MINT, ORACLE_MAX_AGE_SECONDS,
+};
 use crate::errors::ContractError;
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events as _},
+    vec, Address, Env, IntoVal, Vec,
+};
 
 #[test]
 fn test_mint_initial() {
@@ -57,6 +64,51 @@ fn test_mint_initial_only_once() {
     assert_eq!(balance, 1000_0000000);
 }
 
+#[test]
+fn test_total_users_counts_first_mints_only() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    assert_eq!(client.get_total_users(), 0);
+
+    let alice = Address::generate(&env);
+    client.mint_initial(&alice);
+    client.mint_initial(&Address::generate(&env));
+    client.mint_initial(&Address::generate(&env));
+    assert_eq!(client.get_total_users(), 3);
+
+    client.mint_initial(&alice);
+    assert_eq!(client.get_total_users(), 3);
+}
+
+#[test]
+fn test_get_defaults_matches_constants() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let defaults = client.get_defaults();
+    assert_eq!(defaults.bet_window_ledgers, DEFAULT_BET_LEDGERS);
+    assert_eq!(defaults.run_window_ledgers, DEFAULT_RUN_LEDGERS);
+    assert_eq!(defaults.initial_mint, INITIAL_MINT);
+    assert_eq!(defaults.oracle_max_age_secs, ORACLE_MAX_AGE_SECONDS);
+
+    // And with what the contract actually does
+    assert_eq!(defaults.bet_window_ledgers, 6);
+    assert_eq!(defaults.run_window_ledgers, 12);
+    assert_eq!(client.preview_windows(&0), (0, 6, 12));
+
+    env.mock_all_auths();
+    assert_eq!(
+        client.mint_initial(&Address::generate(&env)),
+        defaults.initial_mint
+    );
+    assert_eq!(defaults.oracle_max_age_secs, 300);
+}
+
 #[test]
 fn test_balance_for_new_user() {
     let env = Env::default();
@@ -128,6 +180,33 @@ fn test_initialize_fails_without_admin_auth() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_mint_initial_emits_event_only_on_first_mint() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.mint_initial(&user);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("mint"), user.clone()).into_val(&env),
+                1000_0000000i128.into_val(&env),
+            ),
+        ]
+    );
+
+    // Returning the existing balance is not an onboarding
+    client.mint_initial(&user);
+    assert_eq!(env.events().all(), vec![&env]);
+}
+
 #[test]
 fn test_mint_initial_fails_without_user_auth() {
     let env = Env::default();
@@ -141,3 +220,117 @@ fn test_mint_initial_fails_without_user_auth() {
     let result = client.try_mint_initial(&user);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_balance_batch_mixed_users() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let minted1 = Address::generate(&env);
+    let unminted = Address::generate(&env);
+    let minted2 = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.mint_initial(&minted1);
+    client.mint_initial(&minted2);
+
+    let users = vec![&env, minted1.clone(), unminted.clone(), minted2.clone()];
+    let balances = client.balance_batch(&users);
+
+    // Balances come back in request order, 0 for users who never minted
+    assert_eq!(balances, vec![&env, 1000_0000000, 0, 1000_0000000]);
+}
+
+#[test]
+fn test_balance_batch_empty() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let balances = client.balance_batch(&Vec::new(&env));
+    assert_eq!(balances.len(), 0);
+}
+
+#[test]
+fn test_initialize_admin_equals_oracle_fails() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    // Same key for admin and oracle is rejected
+    let result = client.try_initialize(&admin, &admin);
+    assert_eq!(result, Err(Ok(ContractError::AdminOracleConflict)));
+    assert_eq!(client.get_admin(), None);
+}
+
+#[test]
+fn test_set_oracle_rotation() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let new_oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    // Rotating to the admin address is rejected
+    let result = client.try_set_oracle(&admin);
+    assert_eq!(result, Err(Ok(ContractError::AdminOracleConflict)));
+    assert_eq!(client.get_oracle(), Some(oracle));
+
+    // Rotating to a fresh address succeeds
+    client.set_oracle(&new_oracle);
+    assert_eq!(client.get_oracle(), Some(new_oracle));
+}
+
+#[test]
+fn test_transfer_admin_to_oracle_fails() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    let result = client.try_transfer_admin(&oracle);
+    assert_eq!(result, Err(Ok(ContractError::AdminOracleConflict)));
+    assert_eq!(client.get_admin(), Some(admin));
+
+    client.transfer_admin(&new_admin);
+    assert_eq!(client.get_admin(), Some(new_admin));
+}
+
+#[test]
+fn test_is_initialized_and_is_oracle_set() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    assert!(!client.is_initialized());
+    assert!(!client.is_oracle_set());
+
+    client.initialize(&admin, &oracle);
+
+    assert!(client.is_initialized());
+    assert!(client.is_oracle_set());
+}
@@ -0,0 +1,123 @@
+//! Tests for per-season leaderboards.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::types::{BetSide, LeaderboardEntry, OraclePayload};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    vec, Address, Env,
+};
+
+#[test]
+fn test_leaderboard_resets_each_season() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    assert_eq!(client.get_season(), 0);
+
+    // Season 0: Bob wins a real round, then Alice wins twice
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &10_0000000, &BetSide::Down);
+    client.place_bet(&bob, &10_0000000, &BetSide::Up);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    env.as_contract(&contract_id, || {
+        VirtualTokenContract::_update_stats_win(&env, alice.clone(), 10_0000000);
+        VirtualTokenContract::_update_stats_win(&env, alice.clone(), 10_0000000);
+    });
+
+    let season_0 = vec![
+        &env,
+        LeaderboardEntry {
+            user: alice.clone(),
+            wins: 2,
+        },
+        LeaderboardEntry {
+            user: bob.clone(),
+            wins: 1,
+        },
+    ];
+    assert_eq!(client.get_leaderboard_for_season(&0), season_0);
+
+    // Season 1 starts empty and only Bob wins
+    assert_eq!(client.start_new_season(), 1);
+    assert_eq!(client.get_leaderboard_for_season(&1), vec![&env]);
+
+    env.as_contract(&contract_id, || {
+        VirtualTokenContract::_update_stats_win(&env, bob.clone(), 10_0000000);
+    });
+
+    assert_eq!(
+        client.get_leaderboard_for_season(&1),
+        vec![
+            &env,
+            LeaderboardEntry {
+                user: bob.clone(),
+                wins: 1,
+            },
+        ]
+    );
+
+    // History is preserved and lifetime stats are untouched
+    assert_eq!(client.get_leaderboard_for_season(&0), season_0);
+    assert_eq!(client.get_user_stats(&bob).total_wins, 2);
+}
+
+#[test]
+fn test_leaderboard_keeps_top_ten() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    let players: [Address; 11] = core::array::from_fn(|_| Address::generate(&env));
+
+    env.as_contract(&contract_id, || {
+        // Ten players with one win fill the board
+        for player in players.iter().take(10) {
+            VirtualTokenContract::_update_stats_win(&env, player.clone(), 1);
+        }
+        // An eleventh with a single win doesn't displace a tie
+        VirtualTokenContract::_update_stats_win(&env, players[10].clone(), 1);
+    });
+
+    let board = client.get_leaderboard_for_season(&0);
+    assert_eq!(board.len(), 10);
+    assert!(!board.iter().any(|e| e.user == players[10]));
+
+    // A second win moves it to the top
+    env.as_contract(&contract_id, || {
+        VirtualTokenContract::_update_stats_win(&env, players[10].clone(), 1);
+    });
+
+    let board = client.get_leaderboard_for_season(&0);
+    assert_eq!(board.len(), 10);
+    assert_eq!(
+        board.get(0),
+        Some(LeaderboardEntry {
+            user: players[10].clone(),
+            wins: 2,
+        })
+    );
+}
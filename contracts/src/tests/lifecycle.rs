@@ -2,10 +2,13 @@
 
 use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
 use crate::errors::ContractError;
-use crate::types::{BetSide, DataKey, OraclePayload, Round, UserPosition};
+use crate::types::{
+    BetSide, CurrentPosition, OraclePayload, RoundMode, RoundStatus, ScheduledRound, UserPosition,
+};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger as _},
-    Address, Env, IntoVal, Map,
+    symbol_short,
+    testutils::{Address as _, Events as _, Ledger as _},
+    vec, Address, Env, IntoVal,
 };
 
 #[test]
@@ -38,6 +41,70 @@ fn test_create_round() {
     assert_eq!(round.end_ledger, 12);
 }
 
+#[test]
+fn test_create_round_records_timestamp() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin, &oracle);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1_700_000_000;
+    });
+    client.create_round(&1_5000000, &None);
+
+    // Later ledgers don't change when the round started
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1_700_000_030;
+    });
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.created_timestamp, 1_700_000_000);
+}
+
+#[test]
+fn test_round_timestamps_estimated_from_windows() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin, &oracle);
+    client.set_windows(&10, &30);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1_700_000_000;
+    });
+    client.create_round(&1_5000000, &None);
+
+    // ~5s per ledger: 10 ledgers of betting, 30 until the end
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.bet_end_timestamp, 1_700_000_050);
+    assert_eq!(round.end_timestamp, 1_700_000_150);
+
+    // Closing early moves the estimate to now
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 4;
+        li.timestamp = 1_700_000_020;
+    });
+    client.close_betting();
+    assert_eq!(
+        client.get_active_round().unwrap().bet_end_timestamp,
+        1_700_000_020
+    );
+
+    client.reopen_betting(&20);
+    assert_eq!(
+        client.get_active_round().unwrap().bet_end_timestamp,
+        1_700_000_100
+    );
+}
+
 #[test]
 fn test_create_round_while_active_fails() {
     let env = Env::default();
@@ -208,40 +275,18 @@ fn test_multiple_rounds_lifecycle() {
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
     let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
 
     env.mock_all_auths();
 
     client.initialize(&admin, &oracle);
     client.mint_initial(&alice);
+    client.mint_initial(&bob);
 
     // ROUND 1: Alice bets UP and wins
     client.create_round(&1_0000000, &None);
     client.place_bet(&alice, &100_0000000, &BetSide::Up);
-
-    env.as_contract(&contract_id, || {
-        let mut positions = Map::<Address, UserPosition>::new(&env);
-        positions.set(
-            alice.clone(),
-            UserPosition {
-                amount: 100_0000000,
-                side: BetSide::Up,
-            },
-        );
-        env.storage()
-            .persistent()
-            .set(&DataKey::Positions, &positions);
-
-        let mut round: Round = env
-            .storage()
-            .persistent()
-            .get(&DataKey::ActiveRound)
-            .unwrap();
-        round.pool_up = 100_0000000;
-        round.pool_down = 50_0000000;
-        env.storage()
-            .persistent()
-            .set(&DataKey::ActiveRound, &round);
-    });
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
 
     // Advance ledger to allow resolution
     env.ledger().with_mut(|li| {
@@ -262,31 +307,7 @@ fn test_multiple_rounds_lifecycle() {
     // ROUND 2: Alice bets DOWN and wins again
     client.create_round(&2_0000000, &None);
     client.place_bet(&alice, &100_0000000, &BetSide::Down);
-
-    env.as_contract(&contract_id, || {
-        let mut positions = Map::<Address, UserPosition>::new(&env);
-        positions.set(
-            alice.clone(),
-            UserPosition {
-                amount: 100_0000000,
-                side: BetSide::Down,
-            },
-        );
-        env.storage()
-            .persistent()
-            .set(&DataKey::Positions, &positions);
-
-        let mut round: Round = env
-            .storage()
-            .persistent()
-            .get(&DataKey::ActiveRound)
-            .unwrap();
-        round.pool_up = 80_0000000;
-        round.pool_down = 100_0000000;
-        env.storage()
-            .persistent()
-            .set(&DataKey::ActiveRound, &round);
-    });
+    client.place_bet(&bob, &80_0000000, &BetSide::Up);
 
     // Advance ledger to allow resolution
     env.ledger().with_mut(|li| {
@@ -460,3 +481,600 @@ fn test_claim_winnings_fails_without_user_auth() {
     let result = client.try_claim_winnings(&user);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_protocol_stats_across_two_rounds() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    let stats = client.get_protocol_stats();
+    assert_eq!(stats.total_rounds, 0);
+    assert_eq!(stats.total_volume, 0);
+    assert_eq!(stats.total_fees, 0);
+
+    // Round 1: Up/Down, 150 vXLM wagered
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Round 2: Precision, 30 vXLM wagered
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &10_0000000, &2300);
+    client.place_precision_prediction(&bob, &20_0000000, &2250);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2290,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+
+    let stats = client.get_protocol_stats();
+    assert_eq!(stats.total_rounds, 2);
+    assert_eq!(stats.total_volume, 180_0000000);
+    assert_eq!(stats.total_fees, client.get_fee_treasury());
+}
+
+#[test]
+fn test_round_cooldown_blocks_then_allows_creation() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.set_round_cooldown(&10);
+    assert_eq!(client.get_round_cooldown(), 10);
+
+    // First round is never blocked
+    client.create_round(&1_0000000, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // 9 ledgers after resolution: still cooling down
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 21;
+    });
+    let result = client.try_create_round(&1_0000000, &None);
+    assert_eq!(result, Err(Ok(ContractError::CooldownActive)));
+
+    // 10 ledgers after resolution: allowed
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 22;
+    });
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.get_active_round().unwrap().start_ledger, 22);
+}
+
+#[test]
+fn test_get_active_round_checked() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    assert_eq!(
+        client.try_get_active_round_checked(),
+        Err(Ok(ContractError::NoActiveRound))
+    );
+
+    client.create_round(&1_0000000, &None);
+
+    let round = client.get_active_round_checked();
+    assert_eq!(Some(round.clone()), client.get_active_round());
+    assert_eq!(round.price_start, 1_0000000);
+}
+
+#[test]
+fn test_resolved_round_readable_during_retention() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.set_result_retention(&5);
+    assert_eq!(client.get_result_retention(), 5);
+    assert_eq!(client.get_round_status(), RoundStatus::None);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    assert_eq!(client.get_round_status(), RoundStatus::Open);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    assert_eq!(client.get_round_status(), RoundStatus::Closed);
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    let round = client.get_active_round().unwrap();
+    assert!(round.resolved);
+    assert_eq!(round.final_price, 1_5000000);
+    assert_eq!(round.pool_up, 100_0000000);
+    assert_eq!(client.get_round_status(), RoundStatus::Resolved);
+
+    // Still a finished round: no more bets
+    assert!(client
+        .try_place_bet(&alice, &10_0000000, &BetSide::Up)
+        .is_err());
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 17;
+    });
+    assert!(client.get_active_round().is_some());
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 18;
+    });
+    assert!(client.get_active_round().is_none());
+    assert_eq!(client.get_round_status(), RoundStatus::None);
+}
+
+#[test]
+fn test_resolved_round_removed_by_clear_or_new_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.set_result_retention(&100);
+
+    let resolve = |price: u128, round_id: u32| {
+        client.resolve_round(&OraclePayload {
+            price,
+            timestamp: env.ledger().timestamp(),
+            round_id,
+        });
+    };
+
+    client.create_round(&1_0000000, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    resolve(9000000, 0);
+    assert!(client.get_active_round().unwrap().resolved);
+
+    client.clear_resolved();
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("round"), symbol_short!("cleared")).into_val(&env),
+                0u32.into_val(&env),
+            ),
+        ]
+    );
+    assert!(client.get_active_round().is_none());
+
+    client.create_round(&1_0000000, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    resolve(1_1000000, 12);
+    assert_eq!(client.get_active_round().unwrap().final_price, 1_1000000);
+
+    // The next round replaces the revealed one
+    client.create_round(&1_1000000, &None);
+    let round = client.get_active_round().unwrap();
+    assert!(!round.resolved);
+    assert_eq!(round.final_price, 0);
+}
+
+#[test]
+fn test_resolved_round_cleared_immediately_by_default() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert!(client.get_active_round().is_none());
+}
+
+#[test]
+fn test_get_account_snapshot_through_a_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
+
+    let account = client.get_account(&alice);
+    assert_eq!(account.balance, 900_0000000);
+    assert_eq!(account.pending_winnings, 0);
+    assert_eq!(
+        account.current_position,
+        CurrentPosition::Side(UserPosition {
+            amount: 100_0000000,
+            side: BetSide::Up,
+            round_id: 0,
+        })
+    );
+    assert_eq!(account.stats.total_wins, 0);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    let account = client.get_account(&alice);
+    assert_eq!(account.balance, 900_0000000);
+    assert_eq!(account.pending_winnings, 150_0000000);
+    assert_eq!(account.current_position, CurrentPosition::None);
+    assert_eq!(account.stats.total_wins, 1);
+    assert_eq!(account.stats.biggest_win, 150_0000000);
+
+    let account = client.get_account(&bob);
+    assert_eq!(account.balance, 950_0000000);
+    assert_eq!(account.pending_winnings, 0);
+    assert_eq!(account.stats.total_losses, 1);
+}
+
+#[test]
+fn test_streak_index_tracks_wins_and_losses() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+    assert_eq!(client.get_streaks_above(&0).len(), 0);
+
+    let resolve_at = |ledger: u32, price: u128| {
+        env.ledger().with_mut(|li| {
+            li.sequence_number = ledger;
+        });
+        let round = client.get_active_round().unwrap();
+        client.resolve_round(&OraclePayload {
+            price,
+            timestamp: env.ledger().timestamp(),
+            round_id: round.start_ledger,
+        });
+    };
+
+    // ROUND 1: UP wins, Alice 1
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
+    resolve_at(12, 1_5000000);
+
+    // ROUND 2: DOWN wins, Alice 2 and Carol 1
+    client.create_round(&2_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Down);
+    client.place_bet(&bob, &80_0000000, &BetSide::Up);
+    client.place_bet(&carol, &20_0000000, &BetSide::Down);
+    resolve_at(24, 1_5000000);
+
+    // Longest streak first
+    let hot = client.get_streaks_above(&0);
+    assert_eq!(hot.len(), 2);
+    assert_eq!(hot.get(0), Some((alice.clone(), 2)));
+    assert_eq!(hot.get(1), Some((carol.clone(), 1)));
+
+    let hottest = client.get_streaks_above(&1);
+    assert_eq!(hottest.len(), 1);
+    assert_eq!(hottest.get(0), Some((alice.clone(), 2)));
+
+    // ROUND 3: Alice loses and drops out of the index
+    client.create_round(&1_5000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
+    resolve_at(36, 1_0000000);
+
+    let hot = client.get_streaks_above(&0);
+    assert_eq!(hot.len(), 2);
+    assert!(!hot.iter().any(|(user, _)| user == alice));
+    assert!(hot.iter().any(|entry| entry == (bob.clone(), 1)));
+    assert!(hot.iter().any(|entry| entry == (carol.clone(), 1)));
+}
+
+#[test]
+fn test_streak_index_keeps_longest_streaks() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let hot = Address::generate(&env);
+    let late = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    let win = |user: &Address| {
+        env.as_contract(&contract_id, || {
+            VirtualTokenContract::_update_stats_win(&env, user.clone(), 1);
+        });
+    };
+
+    // Fill the index with 50 single wins
+    for _ in 0..50 {
+        win(&Address::generate(&env));
+    }
+    // A second win is strictly longer and pushes out a single win
+    win(&hot);
+    win(&hot);
+    // Another single win doesn't make the cut
+    win(&late);
+
+    let streaks = client.get_streaks_above(&0);
+    assert_eq!(streaks.len(), 50);
+    assert_eq!(streaks.get(0), Some((hot.clone(), 2)));
+    assert!(!streaks.iter().any(|(user, _)| user == late));
+    assert_eq!(client.get_streaks_above(&1).len(), 1);
+}
+
+#[test]
+fn test_schedule_round_stores_pending_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    assert_eq!(client.get_scheduled_round(), None);
+
+    client.schedule_round(&2297, &Some(1), &50);
+
+    assert_eq!(
+        client.get_scheduled_round(),
+        Some(ScheduledRound {
+            start_price: 2297,
+            mode: 1,
+            start_ledger: 50,
+        })
+    );
+    // Nothing is open until activation
+    assert!(client.get_active_round().is_none());
+
+    assert_eq!(
+        client.try_schedule_round(&0, &None, &50),
+        Err(Ok(ContractError::InvalidPrice))
+    );
+    assert_eq!(
+        client.try_schedule_round(&2297, &Some(2), &50),
+        Err(Ok(ContractError::InvalidMode))
+    );
+}
+
+#[test]
+fn test_activate_scheduled_round_too_early() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    assert_eq!(
+        client.try_activate_scheduled_round(),
+        Err(Ok(ContractError::NoScheduledRound))
+    );
+
+    client.schedule_round(&1_0000000, &None, &50);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 49;
+    });
+    assert_eq!(
+        client.try_activate_scheduled_round(),
+        Err(Ok(ContractError::TooEarlyToActivate))
+    );
+    assert!(client.get_active_round().is_none());
+    assert!(client.get_scheduled_round().is_some());
+}
+
+#[test]
+fn test_activate_scheduled_round_opens_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.schedule_round(&2297, &Some(1), &50);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 55;
+    });
+    client.activate_scheduled_round();
+
+    // Starts at the activation ledger with the default (6, 12) windows
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.price_start, 2297);
+    assert_eq!(round.mode, RoundMode::Precision);
+    assert_eq!(round.start_ledger, 55);
+    assert_eq!(round.bet_end_ledger, 61);
+    assert_eq!(round.end_ledger, 67);
+    assert_eq!(client.get_scheduled_round(), None);
+
+    client.place_precision_prediction(&alice, &10_0000000, &2300);
+    assert!(client.has_bet(&alice));
+}
+
+#[test]
+fn test_cancel_scheduled_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    assert_eq!(
+        client.try_cancel_scheduled_round(),
+        Err(Ok(ContractError::NoScheduledRound))
+    );
+
+    client.schedule_round(&1_0000000, &None, &50);
+    client.cancel_scheduled_round();
+    assert_eq!(client.get_scheduled_round(), None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 50;
+    });
+    assert_eq!(
+        client.try_activate_scheduled_round(),
+        Err(Ok(ContractError::NoScheduledRound))
+    );
+    assert!(client.get_active_round().is_none());
+}
+
+#[test]
+fn test_cancelled_round_id_is_not_reused() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    assert_eq!(client.get_last_round_id(), None);
+    assert_eq!(
+        client.try_cancel_round(),
+        Err(Ok(ContractError::NoActiveRound))
+    );
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 1;
+    });
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+
+    client.cancel_round();
+    assert!(client.get_active_round().is_none());
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+    assert_eq!(client.get_last_round_id(), Some(1));
+    assert_eq!(client.get_protocol_stats().total_rounds, 0);
+
+    // Round 1 is used up, even though it never resolved
+    assert_eq!(
+        client.try_create_round(&1_0000000, &None),
+        Err(Ok(ContractError::RoundIdTaken))
+    );
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 2;
+    });
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.get_active_round().unwrap().start_ledger, 2);
+    assert_eq!(client.get_last_round_id(), Some(2));
+}
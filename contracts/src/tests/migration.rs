@@ -0,0 +1,207 @@
+//! Tests for migrating storage written under legacy keys.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::errors::ContractError;
+use crate::types::{BetSide, DataKey, LegacyUserPosition, OraclePayload, Round};
+use soroban_sdk::{
+    contracttype,
+    testutils::{Address as _, Ledger as _},
+    Address, Env, Map,
+};
+
+/// Round as an earlier release stored it, before the advisory timestamps
+#[contracttype]
+#[derive(Clone)]
+struct LegacyRound {
+    price_start: u128,
+    start_ledger: u32,
+    end_ledger: u32,
+    pool_up: i128,
+    pool_down: i128,
+}
+
+#[test]
+fn test_migrate_moves_legacy_positions() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    assert_eq!(client.migrate_v1_to_v2(), 0);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 5;
+    });
+    client.create_round(&1_0000000, &None);
+
+    // Seed storage the way the single-module contract wrote it: Positions only, no round id
+    env.as_contract(&contract_id, || {
+        let mut legacy: Map<Address, LegacyUserPosition> = Map::new(&env);
+        legacy.set(
+            alice.clone(),
+            LegacyUserPosition {
+                amount: 100_0000000,
+                side: BetSide::Up,
+            },
+        );
+        legacy.set(
+            bob.clone(),
+            LegacyUserPosition {
+                amount: 50_0000000,
+                side: BetSide::Down,
+            },
+        );
+        env.storage().persistent().set(&DataKey::Positions, &legacy);
+
+        let mut round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .unwrap();
+        round.pool_up = 100_0000000;
+        round.pool_down = 50_0000000;
+        env.storage()
+            .persistent()
+            .set(&DataKey::ActiveRound, &round);
+    });
+    assert_eq!(client.get_user_position(&alice), None);
+
+    assert_eq!(client.migrate_v1_to_v2(), 2);
+    assert_eq!(
+        client.get_user_position(&alice).unwrap().amount,
+        100_0000000
+    );
+    assert_eq!(client.get_user_position(&bob).unwrap().side, BetSide::Down);
+    assert_eq!(client.get_user_position(&bob).unwrap().round_id, 5);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&DataKey::Positions));
+    });
+
+    // The migrated round resolves normally
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 17;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 5,
+    });
+    assert_eq!(client.get_pending_winnings(&alice), 150_0000000);
+}
+
+#[test]
+fn test_migrate_requires_admin() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_migrate_v1_to_v2(),
+        Err(Ok(ContractError::AdminNotSet))
+    );
+}
+
+#[test]
+fn test_purge_orphaned_positions_refunds_stale_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&bob, &30_0000000, &BetSide::Up);
+    client.place_bet(&carol, &20_0000000, &BetSide::Down);
+
+    assert_eq!(
+        client.try_purge_orphaned_positions(&0),
+        Err(Ok(ContractError::RoundAlreadyActive))
+    );
+
+    // An upgrade dropped round 0 without resolving it, leaving its positions behind
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().remove(&DataKey::ActiveRound);
+    });
+
+    // Each stake comes back exactly once, even though bets sit in both position maps
+    assert_eq!(client.purge_orphaned_positions(&7), 0);
+    assert_eq!(client.purge_orphaned_positions(&0), 2);
+    assert_eq!(client.get_pending_winnings(&bob), 30_0000000);
+    assert_eq!(client.get_pending_winnings(&carol), 20_0000000);
+    assert_eq!(
+        client.balance(&bob) + client.get_pending_winnings(&bob),
+        1000_0000000
+    );
+    assert_eq!(client.get_user_position(&bob), None);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&DataKey::Positions));
+        assert!(!env.storage().persistent().has(&DataKey::UpDownPositions));
+    });
+
+    assert_eq!(client.purge_orphaned_positions(&0), 0);
+    assert_eq!(client.get_pending_winnings(&bob), 30_0000000);
+}
+
+#[test]
+fn test_get_active_round_safe_reports_old_schema() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    assert_eq!(client.get_active_round_safe(), None);
+
+    client.create_round(&1_0000000, &None);
+    assert_eq!(
+        client.get_active_round_safe(),
+        Some(client.get_active_round().unwrap())
+    );
+
+    // A round left over from a partial upgrade
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(
+            &DataKey::ActiveRound,
+            &LegacyRound {
+                price_start: 1_0000000,
+                start_ledger: 0,
+                end_ledger: 12,
+                pool_up: 0,
+                pool_down: 0,
+            },
+        );
+    });
+    assert_eq!(
+        client.try_get_active_round_safe(),
+        Err(Ok(ContractError::StateDecodeError))
+    );
+
+    // Not a round at all
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(&DataKey::ActiveRound, &7u32);
+    });
+    assert_eq!(
+        client.try_get_active_round_safe(),
+        Err(Ok(ContractError::StateDecodeError))
+    );
+}
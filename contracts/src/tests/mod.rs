@@ -1,11 +1,21 @@
 //! Test modules for the XLM Price Prediction Market contract.
 
 mod betting;
+mod blacklist;
+mod checkpoints;
+mod claims;
+mod collateral;
 mod edge_cases;
+mod history;
 mod initialization;
+mod leaderboard;
 mod lifecycle;
+mod migration;
 mod mode_tests;
 mod property_invariants;
+mod regulated;
 mod resolution;
 mod security;
+mod settlement;
+mod testnet;
 mod windows;
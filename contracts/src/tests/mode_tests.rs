@@ -2,10 +2,10 @@
 
 use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
 use crate::errors::ContractError;
-use crate::types::{BetSide, OraclePayload, RoundMode};
+use crate::types::{BetSide, DataKey, OraclePayload, PrecisionPrediction, RoundMode, UserPosition};
 use soroban_sdk::{
     testutils::{Address as _, Events, Ledger as _},
-    Address, Env,
+    vec, Address, Env, Map,
 };
 
 #[test]
@@ -135,6 +135,45 @@ fn test_place_bet_on_precision_mode_fails() {
     assert_eq!(result, Err(Ok(ContractError::WrongModeForPrediction)));
 }
 
+#[test]
+fn test_wrong_mode_entry_points_leave_funds_untouched() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&user);
+
+    // Up/Down entry points on a Precision round
+    client.create_round(&2297, &Some(1));
+    let result = client.try_place_bet_partial(&user, &100_0000000, &BetSide::Up);
+    assert_eq!(result, Err(Ok(ContractError::WrongModeForPrediction)));
+    assert_eq!(client.get_updown_positions().len(), 0);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2297,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Precision entry points on an Up/Down round
+    client.create_round(&1_0000000, &Some(0));
+    let result = client.try_predict_price(&user, &2297, &100_0000000);
+    assert_eq!(result, Err(Ok(ContractError::WrongModeForPrediction)));
+    assert_eq!(client.get_precision_predictions().len(), 0);
+
+    assert_eq!(client.balance(&user), 1000_0000000);
+}
+
 #[test]
 fn test_place_precision_prediction_on_precision_mode() {
     let env = Env::default();
@@ -165,6 +204,92 @@ fn test_place_precision_prediction_on_precision_mode() {
     assert_eq!(client.balance(&user), 900_0000000);
 }
 
+#[test]
+fn test_default_mode_applies_when_mode_omitted() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&user);
+
+    assert_eq!(client.get_default_mode(), 0);
+    assert_eq!(
+        client.try_set_default_mode(&2),
+        Err(Ok(ContractError::InvalidMode))
+    );
+
+    client.set_default_mode(&1);
+    assert_eq!(client.get_default_mode(), 1);
+
+    client.create_round(&2297, &None);
+    assert_eq!(
+        client.get_active_round().unwrap().mode,
+        RoundMode::Precision
+    );
+    client.place_precision_prediction(&user, &10_0000000, &2300);
+
+    // An explicit mode still wins over the default
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2300,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    client.create_round(&1_0000000, &Some(0));
+    assert_eq!(client.get_active_round().unwrap().mode, RoundMode::UpDown);
+}
+
+#[test]
+fn test_get_user_precision_prediction_reads_back_entry() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &25_0000000, &2300);
+    client.place_precision_prediction(&bob, &40_0000000, &2250);
+
+    assert_eq!(
+        client.get_user_precision_prediction(&alice),
+        Some(PrecisionPrediction {
+            user: alice.clone(),
+            predicted_price: 2300,
+            amount: 25_0000000,
+        })
+    );
+    assert_eq!(
+        client
+            .get_user_precision_prediction(&bob)
+            .unwrap()
+            .predicted_price,
+        2250
+    );
+
+    // Non-participants have no entry
+    assert_eq!(client.get_user_precision_prediction(&outsider), None);
+}
+
 #[test]
 fn test_place_precision_prediction_on_updown_mode_fails() {
     let env = Env::default();
@@ -214,6 +339,37 @@ fn test_precision_prediction_already_bet() {
     assert_eq!(result, Err(Ok(ContractError::AlreadyBet)));
 }
 
+#[test]
+fn test_rejected_second_prediction_leaves_first_intact() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&user);
+
+    client.create_round(&1_0000000, &Some(1));
+    client.place_precision_prediction(&user, &100_0000000, &2297);
+
+    // Same price or a different one, the user keeps exactly one entry
+    for price in [2297u128, 2500] {
+        let result = client.try_place_precision_prediction(&user, &50_0000000, &price);
+        assert_eq!(result, Err(Ok(ContractError::AlreadyBet)));
+    }
+
+    let predictions = client.get_precision_predictions();
+    assert_eq!(predictions.len(), 1);
+    assert_eq!(predictions.get(0).unwrap().predicted_price, 2297);
+    assert_eq!(predictions.get(0).unwrap().amount, 100_0000000);
+    assert_eq!(client.balance(&user), 900_0000000);
+}
+
 #[test]
 fn test_get_precision_predictions() {
     let env = Env::default();
@@ -523,3 +679,165 @@ fn test_predict_price_event_emission() {
     assert!(!events.is_empty());
     assert!(!events.is_empty());
 }
+
+#[test]
+fn test_precision_landscape_aggregates_stakes_by_price() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let dave = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    for user in [&alice, &bob, &carol, &dave] {
+        client.mint_initial(user);
+    }
+
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.get_precision_landscape().len(), 0);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2400);
+    client.place_precision_prediction(&bob, &50_0000000, &2297);
+    client.place_precision_prediction(&carol, &30_0000000, &2350);
+    client.place_precision_prediction(&dave, &20_0000000, &2297);
+
+    assert_eq!(
+        client.get_precision_landscape(),
+        vec![
+            &env,
+            (2297u128, 70_0000000i128),
+            (2350u128, 30_0000000i128),
+            (2400u128, 100_0000000i128),
+        ]
+    );
+}
+
+#[test]
+fn test_position_count_for_each_mode() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    for user in [&alice, &bob, &carol] {
+        client.mint_initial(user);
+    }
+    assert_eq!(client.get_position_count(), 0);
+
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.get_position_count(), 0);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
+    assert_eq!(client.get_position_count(), 2);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_position_count(), 0);
+
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &10_0000000, &2300);
+    client.place_precision_prediction(&bob, &10_0000000, &2310);
+    client.place_precision_prediction(&carol, &10_0000000, &2320);
+    assert_eq!(client.get_position_count(), 3);
+}
+
+#[test]
+fn test_has_bet_updown() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    assert!(!client.has_bet(&alice));
+
+    client.create_round(&1_0000000, &None);
+    assert!(!client.has_bet(&alice));
+
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    assert!(client.has_bet(&alice));
+    assert!(!client.has_bet(&outsider));
+
+    // A position left over from an earlier round doesn't count
+    env.as_contract(&contract_id, || {
+        let mut positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UpDownPositions)
+            .unwrap();
+        positions.set(
+            outsider.clone(),
+            UserPosition {
+                amount: 10_0000000,
+                side: BetSide::Down,
+                round_id: 7,
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::UpDownPositions, &positions);
+    });
+    assert!(!client.has_bet(&outsider));
+}
+
+#[test]
+fn test_has_bet_precision() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+
+    client.create_round(&2297, &Some(1));
+    assert!(!client.has_bet(&alice));
+
+    client.place_precision_prediction(&alice, &10_0000000, &2300);
+    assert!(client.has_bet(&alice));
+    assert!(!client.has_bet(&outsider));
+}
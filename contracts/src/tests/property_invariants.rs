@@ -4,8 +4,11 @@
 //! - Conservation of value (no payouts exceed the total pot)
 //! - Non-negative pending winnings and balances
 //! - Monotonic user statistics (wins, losses, and best streak never decrease)
+//! - Solvency (vXLM supply covers balances, stakes and pending winnings)
+//! - Extreme amounts either settle conserved or fail with `Overflow`, never panic or wrap
 
 use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::errors::ContractError;
 use crate::types::{
     BetSide, DataKey, OraclePayload, PrecisionPrediction, Round, UserPosition, UserStats,
 };
@@ -61,6 +64,7 @@ proptest! {
                 positions.set(alice.clone(), UserPosition {
                     amount: a_up,
                     side: BetSide::Up,
+                    round_id: 0,
                 });
             }
 
@@ -68,6 +72,7 @@ proptest! {
                 positions.set(bob.clone(), UserPosition {
                     amount: b_up,
                     side: BetSide::Up,
+                    round_id: 0,
                 });
             }
 
@@ -75,6 +80,7 @@ proptest! {
                 positions.set(charlie.clone(), UserPosition {
                     amount: c_down,
                     side: BetSide::Down,
+                    round_id: 0,
                 });
             }
 
@@ -120,6 +126,95 @@ proptest! {
     }
 }
 
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    /// Up/Down mode at extreme scale: with stakes up to i128::MAX / 2 the pot itself
+    /// may not fit in an i128. Resolution must then fail with `Overflow`; when it
+    /// succeeds, winners share exactly what their stakes and the losing pool allow.
+    #[test]
+    fn updown_extreme_amounts_conserve_or_overflow(
+        a_up in 1i128..=i128::MAX / 2,
+        b_up in 1i128..=i128::MAX / 2,
+        c_down in 1i128..=i128::MAX / 2,
+    ) {
+        let total_up = a_up + b_up;
+
+        let env = Env::default();
+        let contract_id = env.register(VirtualTokenContract, ());
+        let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let oracle = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(&admin, &oracle);
+        client.create_round(&1_0000000, &None);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let charlie = Address::generate(&env);
+
+        env.as_contract(&contract_id, || {
+            let mut positions = Map::<Address, UserPosition>::new(&env);
+            positions.set(alice.clone(), UserPosition {
+                amount: a_up,
+                side: BetSide::Up,
+                round_id: 0,
+            });
+            positions.set(bob.clone(), UserPosition {
+                amount: b_up,
+                side: BetSide::Up,
+                round_id: 0,
+            });
+            positions.set(charlie.clone(), UserPosition {
+                amount: c_down,
+                side: BetSide::Down,
+                round_id: 0,
+            });
+            env.storage().persistent().set(&DataKey::UpDownPositions, &positions);
+
+            let mut round: Round = env.storage().persistent().get(&DataKey::ActiveRound).unwrap();
+            round.pool_up = total_up;
+            round.pool_down = c_down;
+            env.storage().persistent().set(&DataKey::ActiveRound, &round);
+        });
+
+        env.ledger().with_mut(|li| {
+            li.sequence_number = 12;
+        });
+
+        let result = client.try_resolve_round(&OraclePayload {
+            price: 2_0000000,
+            timestamp: env.ledger().timestamp(),
+            round_id: 0,
+        });
+
+        match result {
+            Ok(Ok(())) => {
+                let alice_pending = client.get_pending_winnings(&alice);
+                let bob_pending = client.get_pending_winnings(&bob);
+
+                // Each winner gets at least their stake, never more than stake + losing pool
+                prop_assert!(alice_pending >= a_up && alice_pending - a_up <= c_down);
+                prop_assert!(bob_pending >= b_up && bob_pending - b_up <= c_down);
+                prop_assert_eq!(client.get_pending_winnings(&charlie), 0);
+
+                // Shares together never exceed the losing pool (checked in u128 to avoid wrapping)
+                let shares = (alice_pending - a_up) as u128 + (bob_pending - b_up) as u128;
+                prop_assert!(shares <= c_down as u128);
+            }
+            Err(Ok(error)) => {
+                prop_assert_eq!(error, ContractError::Overflow);
+                // A failed resolution leaves the round untouched
+                prop_assert!(client.get_active_round().is_some());
+                prop_assert_eq!(client.get_pending_winnings(&alice), 0);
+            }
+            other => prop_assert!(false, "resolution panicked: {:?}", other),
+        }
+    }
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(32))]
 
@@ -246,7 +341,7 @@ proptest! {
                 let before: UserStats = VirtualTokenContract::get_user_stats(env.clone(), user.clone());
 
                 if outcome {
-                    VirtualTokenContract::_update_stats_win(&env, user.clone());
+                    VirtualTokenContract::_update_stats_win(&env, user.clone(), 1);
                 } else {
                     VirtualTokenContract::_update_stats_loss(&env, user.clone());
                 }
@@ -270,3 +365,76 @@ proptest! {
         });
     }
 }
+
+#[test]
+fn solvency_holds_across_rounds() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.deposit(&carol, &300_0000000);
+    client.set_fee_bps(&500);
+    client.set_consolation_bps(&1_000);
+    assert!(client.check_solvency());
+
+    let resolve = |price: u128, round_id: u32| {
+        env.ledger().with_mut(|li| {
+            li.sequence_number = round_id + 12;
+        });
+        client.resolve_round(&OraclePayload {
+            price,
+            timestamp: env.ledger().timestamp(),
+            round_id,
+        });
+    };
+
+    // Up/Down with a fee and consolation, then a claim
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &70_0000000, &BetSide::Down);
+    client.place_bet(&carol, &33_3333333, &BetSide::Up);
+    assert!(client.check_solvency());
+    resolve(1_2000000, 0);
+    assert!(client.check_solvency());
+    client.claim_winnings(&alice);
+    assert!(client.check_solvency());
+
+    // Precision round
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &40_0000000, &2300);
+    client.place_precision_prediction(&bob, &60_0000000, &2400);
+    resolve(2301, 12);
+    assert!(client.check_solvency());
+
+    // Nobody on the winning side: the stakes leave circulation
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
+    resolve(1_5000000, 24);
+    assert!(client.check_solvency());
+
+    client.withdraw(&carol, &100_0000000);
+    assert!(client.check_solvency());
+
+    // Owing more than exists is reported
+    env.as_contract(&contract_id, || {
+        let pending: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingTotal)
+            .unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingTotal, &(pending + 1000_0000000));
+    });
+    assert!(!client.check_solvency());
+}
@@ -0,0 +1,155 @@
+//! Tests for admin powers behind the `regulated` feature.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+#[cfg(feature = "regulated")]
+#[test]
+fn test_admin_force_transfer() {
+    use crate::errors::ContractError;
+    use soroban_sdk::{symbol_short, testutils::Events, vec, IntoVal};
+
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&from);
+
+    client.admin_force_transfer(&from, &to, &300_0000000);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("admin"), symbol_short!("forced")).into_val(&env),
+                (admin.clone(), from.clone(), to.clone(), 300_0000000i128).into_val(&env),
+            ),
+        ]
+    );
+
+    assert_eq!(client.balance(&from), 700_0000000);
+    assert_eq!(client.balance(&to), 300_0000000);
+
+    let result = client.try_admin_force_transfer(&from, &to, &700_0000001);
+    assert_eq!(result, Err(Ok(ContractError::InsufficientBalance)));
+    assert_eq!(client.balance(&from), 700_0000000);
+}
+
+#[cfg(not(feature = "regulated"))]
+#[test]
+fn test_admin_force_transfer_absent_without_feature() {
+    use soroban_sdk::{vec, IntoVal, InvokeError, Symbol};
+
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&from);
+
+    let result = env.try_invoke_contract::<(), InvokeError>(
+        &contract_id,
+        &Symbol::new(&env, "admin_force_transfer"),
+        vec![
+            &env,
+            from.into_val(&env),
+            to.into_val(&env),
+            300_0000000i128.into_val(&env),
+        ],
+    );
+    assert!(result.is_err());
+    assert_eq!(client.balance(&from), 1000_0000000);
+}
+
+#[cfg(feature = "regulated")]
+#[test]
+fn test_adjust_balance_tracks_supply() {
+    use crate::errors::ContractError;
+    use soroban_sdk::{symbol_short, testutils::Events, vec, IntoVal};
+
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&user);
+    assert_eq!(client.get_total_supply(), 1000_0000000);
+
+    assert_eq!(client.adjust_balance(&user, &250_0000000), 1250_0000000);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("admin"), symbol_short!("adjusted")).into_val(&env),
+                (admin.clone(), user.clone(), 250_0000000i128).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.get_total_supply(), 1250_0000000);
+
+    assert_eq!(client.adjust_balance(&user, &-1000_0000000), 250_0000000);
+    assert_eq!(client.get_total_supply(), 250_0000000);
+    assert!(client.check_solvency());
+
+    // Never below zero
+    assert_eq!(
+        client.try_adjust_balance(&user, &-250_0000001),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+    assert_eq!(
+        client.try_adjust_balance(&user, &0),
+        Err(Ok(ContractError::InvalidBetAmount))
+    );
+    assert_eq!(client.balance(&user), 250_0000000);
+}
+
+#[cfg(not(feature = "regulated"))]
+#[test]
+fn test_adjust_balance_absent_without_feature() {
+    use soroban_sdk::{vec, IntoVal, InvokeError, Symbol};
+
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&user);
+
+    let result = env.try_invoke_contract::<i128, InvokeError>(
+        &contract_id,
+        &Symbol::new(&env, "adjust_balance"),
+        vec![&env, user.into_val(&env), 500_0000000i128.into_val(&env)],
+    );
+    assert!(result.is_err());
+    assert_eq!(client.balance(&user), 1000_0000000);
+}
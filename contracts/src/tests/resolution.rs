@@ -2,10 +2,13 @@
 
 use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
 use crate::errors::ContractError;
-use crate::types::{BetSide, DataKey, OraclePayload, PrecisionPrediction, Round, UserPosition};
+use crate::types::{
+    BetSide, ConfigKey, DataKey, OraclePayload, PrecisionPrediction, Round, UserPosition,
+};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger as _},
-    Address, Env, Map,
+    symbol_short,
+    testutils::{Address as _, Events as _, Ledger as _},
+    vec, Address, Env, IntoVal, Map, Vec,
 };
 
 #[test]
@@ -40,6 +43,7 @@ fn test_resolve_round_price_unchanged() {
             UserPosition {
                 amount: 100_0000000,
                 side: BetSide::Up,
+                round_id: 0,
             },
         );
         positions.set(
@@ -47,6 +51,7 @@ fn test_resolve_round_price_unchanged() {
             UserPosition {
                 amount: 50_0000000,
                 side: BetSide::Down,
+                round_id: 0,
             },
         );
 
@@ -136,6 +141,7 @@ fn test_resolve_round_price_went_up() {
             UserPosition {
                 amount: 100_0000000,
                 side: BetSide::Up,
+                round_id: 0,
             },
         );
         positions.set(
@@ -143,6 +149,7 @@ fn test_resolve_round_price_went_up() {
             UserPosition {
                 amount: 200_0000000,
                 side: BetSide::Up,
+                round_id: 0,
             },
         );
         positions.set(
@@ -150,6 +157,7 @@ fn test_resolve_round_price_went_up() {
             UserPosition {
                 amount: 150_0000000,
                 side: BetSide::Down,
+                round_id: 0,
             },
         );
 
@@ -237,6 +245,7 @@ fn test_resolve_round_price_went_down() {
             UserPosition {
                 amount: 200_0000000,
                 side: BetSide::Down,
+                round_id: 0,
             },
         );
         positions.set(
@@ -244,6 +253,7 @@ fn test_resolve_round_price_went_down() {
             UserPosition {
                 amount: 100_0000000,
                 side: BetSide::Up,
+                round_id: 0,
             },
         );
 
@@ -324,7 +334,7 @@ fn test_user_stats_tracking() {
 
     // Simulate a win
     env.as_contract(&contract_id, || {
-        VirtualTokenContract::_update_stats_win(&env, alice.clone());
+        VirtualTokenContract::_update_stats_win(&env, alice.clone(), 100_0000000);
     });
 
     let stats = client.get_user_stats(&alice);
@@ -334,7 +344,7 @@ fn test_user_stats_tracking() {
 
     // Another win - streak increases
     env.as_contract(&contract_id, || {
-        VirtualTokenContract::_update_stats_win(&env, alice.clone());
+        VirtualTokenContract::_update_stats_win(&env, alice.clone(), 100_0000000);
     });
 
     let stats = client.get_user_stats(&alice);
@@ -390,6 +400,8 @@ fn test_resolve_precision_closest_guess_wins() {
     env.mock_all_auths();
 
     client.initialize(&admin, &oracle);
+    // Closest guess wins however far off
+    client.set_precision_tolerance(&Some(u128::MAX));
 
     // Create Precision mode round starting at 2000
     client.create_round(&2000, &Some(1));
@@ -482,6 +494,8 @@ fn test_resolve_precision_tie_splits_pot() {
     env.mock_all_auths();
 
     client.initialize(&admin, &oracle);
+    // Closest guess wins however far off
+    client.set_precision_tolerance(&Some(u128::MAX));
 
     // Create Precision mode round
     client.create_round(&2000, &Some(1));
@@ -665,6 +679,8 @@ fn test_resolve_precision_three_way_tie() {
     env.mock_all_auths();
 
     client.initialize(&admin, &oracle);
+    // Closest guess wins however far off
+    client.set_precision_tolerance(&Some(u128::MAX));
 
     client.create_round(&2000, &Some(1));
 
@@ -794,6 +810,8 @@ fn test_resolve_precision_large_differences() {
     env.mock_all_auths();
 
     client.initialize(&admin, &oracle);
+    // Closest guess wins however far off
+    client.set_precision_tolerance(&Some(u128::MAX));
 
     client.create_round(&100_0000, &Some(1));
 
@@ -1108,3 +1126,1444 @@ fn test_precision_no_remainder() {
     assert_eq!(client.get_pending_winnings(&alice), 50_0000000);
     assert_eq!(client.get_pending_winnings(&bob), 50_0000000);
 }
+
+#[test]
+fn test_precision_no_guess_within_tolerance_refunds_all() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    // Exact guesses only
+    client.set_precision_tolerance(&Some(0));
+    assert_eq!(client.get_precision_tolerance(), Some(0));
+
+    client.create_round(&2000, &Some(1));
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+
+    client.place_precision_prediction(&alice, &100_0000000, &2200);
+    client.place_precision_prediction(&bob, &50_0000000, &2300);
+    client.place_precision_prediction(&charlie, &30_0000000, &2249);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+
+    // Nobody guessed 2250 exactly
+    client.resolve_round(&OraclePayload {
+        price: 2250,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 50_0000000);
+    assert_eq!(client.get_pending_winnings(&charlie), 30_0000000);
+}
+
+#[test]
+fn test_precision_without_tolerance_pays_exact_guesses_only() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    assert_eq!(client.get_precision_tolerance(), None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Bob is 1 off: no winner, both refunded
+    client.create_round(&2000, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2200);
+    client.place_precision_prediction(&bob, &50_0000000, &2249);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2250,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 50_0000000);
+
+    // Bob's exact guess takes the pot
+    client.create_round(&2000, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2200);
+    client.place_precision_prediction(&bob, &50_0000000, &2250);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2250,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 200_0000000);
+}
+
+#[test]
+fn test_precision_distance_mode_changes_winners() {
+    // Same guesses resolved under both distance modes against a large final price (9000.0000)
+    let final_price: u128 = 90_000_000;
+    let mut pending = [(0i128, 0i128); 2];
+
+    for mode in 0..2u32 {
+        let env = Env::default();
+        let contract_id = env.register(VirtualTokenContract, ());
+        let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let oracle = Address::generate(&env);
+        env.mock_all_auths();
+
+        client.initialize(&admin, &oracle);
+        // Closest guess wins however far off
+        client.set_precision_tolerance(&Some(u128::MAX));
+        client.set_precision_distance_mode(&mode);
+        assert_eq!(client.get_precision_distance_mode(), mode);
+
+        client.create_round(&final_price, &Some(1));
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        client.mint_initial(&alice);
+        client.mint_initial(&bob);
+
+        // Alice is 3 stroops off, Bob 900: both well under one basis point
+        client.place_precision_prediction(&alice, &100_0000000, &(final_price - 3));
+        client.place_precision_prediction(&bob, &100_0000000, &(final_price + 900));
+
+        env.ledger().with_mut(|li| {
+            li.sequence_number = 12;
+        });
+
+        client.resolve_round(&OraclePayload {
+            price: final_price,
+            timestamp: env.ledger().timestamp(),
+            round_id: 0,
+        });
+
+        pending[mode as usize] = (
+            client.get_pending_winnings(&alice),
+            client.get_pending_winnings(&bob),
+        );
+    }
+
+    // Absolute: Alice is strictly closest and takes the pot
+    assert_eq!(pending[0], (200_0000000, 0));
+    // Percent: both are 0 bps away, so they tie and split it
+    assert_eq!(pending[1], (100_0000000, 100_0000000));
+}
+
+#[test]
+fn test_set_precision_distance_mode_rejects_unknown() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    let result = client.try_set_precision_distance_mode(&2);
+    assert_eq!(result, Err(Ok(ContractError::InvalidMode)));
+    assert_eq!(client.get_precision_distance_mode(), 0);
+}
+
+#[test]
+fn test_precision_guess_within_tolerance_wins() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    client.set_precision_tolerance(&Some(5));
+
+    client.create_round(&2000, &Some(1));
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.place_precision_prediction(&alice, &100_0000000, &2247);
+    client.place_precision_prediction(&bob, &100_0000000, &2200);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+
+    client.resolve_round(&OraclePayload {
+        price: 2250,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Alice is 3 away, inside the tolerance of 5
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 0);
+
+    // Clearing the tolerance restores closest-wins
+    client.set_precision_tolerance(&None);
+    assert_eq!(client.get_precision_tolerance(), None);
+}
+
+#[test]
+fn test_draw_band_refunds_small_move() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // 1% band around a 1.0 XLM start price
+    client.set_draw_band_bps(&100);
+    assert_eq!(client.get_draw_band_bps(), 100);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+
+    // +0.5% is inside the band: everyone is refunded
+    client.resolve_round(&OraclePayload {
+        price: 1_0050000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 50_0000000);
+    assert_eq!(client.get_user_stats(&alice).total_wins, 0);
+    assert_eq!(client.get_user_stats(&bob).total_losses, 0);
+}
+
+#[test]
+fn test_draw_band_larger_move_resolves_normally() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.set_draw_band_bps(&100);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+
+    // -2% is outside the band: DOWN wins the whole pot
+    client.resolve_round(&OraclePayload {
+        price: 9800000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert_eq!(client.get_pending_winnings(&bob), 150_0000000);
+}
+
+#[test]
+fn test_set_draw_band_rejects_over_100_percent() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    let result = client.try_set_draw_band_bps(&10_001);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBasisPoints)));
+    assert_eq!(client.get_draw_band_bps(), 0);
+}
+
+#[test]
+fn test_min_participants_single_bettor_refunded() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.set_min_participants_to_pay(&2);
+    assert_eq!(client.get_min_participants_to_pay(), 2);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Refunded, not counted as a win
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+    assert_eq!(client.get_user_stats(&alice).total_wins, 0);
+}
+
+#[test]
+fn test_min_participants_met_pays_normally() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_min_participants_to_pay(&2);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 0);
+    assert_eq!(client.get_user_stats(&alice).total_wins, 1);
+}
+
+#[test]
+fn test_min_participants_refunds_precision_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.set_min_participants_to_pay(&2);
+
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &50_0000000, &2300);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2300,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert_eq!(client.get_pending_winnings(&alice), 50_0000000);
+    assert_eq!(client.get_user_stats(&alice).total_wins, 0);
+}
+
+#[test]
+fn test_biggest_win_keeps_largest_payout() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // (alice_stake, bob_stake, final_price) per round; Alice always bets UP
+    let rounds = [
+        (100_0000000, 200_0000000, 1_5000000), // Alice wins 300
+        (50_0000000, 50_0000000, 1_5000000),   // Alice wins 100
+        (500_0000000, 0, 1_0000000),           // Price unchanged: refund 500
+    ];
+
+    for (i, (alice_stake, bob_stake, final_price)) in rounds.iter().enumerate() {
+        let round_id = i as u32 * 12;
+        client.create_round(&1_0000000, &None);
+        client.place_bet(&alice, alice_stake, &BetSide::Up);
+        if *bob_stake > 0 {
+            client.place_bet(&bob, bob_stake, &BetSide::Down);
+        }
+
+        env.ledger().with_mut(|li| {
+            li.sequence_number = round_id + 12;
+        });
+        client.resolve_round(&OraclePayload {
+            price: *final_price,
+            timestamp: env.ledger().timestamp(),
+            round_id,
+        });
+    }
+
+    let stats = client.get_user_stats(&alice);
+    assert_eq!(stats.total_wins, 2);
+    assert_eq!(stats.biggest_win, 300_0000000);
+    assert_eq!(client.get_user_stats(&bob).biggest_win, 0);
+}
+
+#[test]
+fn test_consolation_paid_to_losers_from_losing_pool() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+
+    assert_eq!(
+        client.try_set_consolation_bps(&10_001),
+        Err(Ok(ContractError::InvalidBasisPoints))
+    );
+    client.set_consolation_bps(&1_000); // 10%
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down);
+    client.place_bet(&carol, &50_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Losers get 10% of their own stake back
+    assert_eq!(client.get_pending_winnings(&bob), 10_0000000);
+    assert_eq!(client.get_pending_winnings(&carol), 5_0000000);
+
+    // Winner splits the remaining 135 of the 150 losing pool
+    assert_eq!(client.get_pending_winnings(&alice), 235_0000000);
+
+    // Total paid out equals total staked
+    let total_paid = client.get_pending_winnings(&alice)
+        + client.get_pending_winnings(&bob)
+        + client.get_pending_winnings(&carol);
+    assert_eq!(total_paid, 250_0000000);
+
+    // A consolation is still a loss
+    assert_eq!(client.get_user_stats(&bob).total_losses, 1);
+    assert_eq!(client.get_user_stats(&bob).total_wins, 0);
+}
+
+#[test]
+fn test_inverse_distance_payout_favours_closer_guess() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+
+    assert_eq!(
+        client.try_set_precision_payout_mode(&4),
+        Err(Ok(ContractError::InvalidMode))
+    );
+    client.set_precision_payout_mode(&1);
+    assert_eq!(client.get_precision_payout_mode(), 1);
+
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &50_0000000, &2300); // 2 away
+    client.place_precision_prediction(&bob, &50_0000000, &2320); // 18 away
+    client.place_precision_prediction(&carol, &30_0000000, &2400); // 98 away
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2302,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    let alice_payout = client.get_pending_winnings(&alice);
+    let bob_payout = client.get_pending_winnings(&bob);
+    let carol_payout = client.get_pending_winnings(&carol);
+
+    // Closer guesses earn more, but nobody is shut out
+    assert!(alice_payout > bob_payout);
+    assert!(bob_payout > carol_payout);
+    assert!(carol_payout > 0);
+
+    // Whole pot is paid out, no more
+    assert_eq!(alice_payout + bob_payout + carol_payout, 130_0000000);
+
+    assert_eq!(client.get_user_stats(&alice).total_wins, 1);
+    assert_eq!(client.get_user_stats(&carol).total_losses, 1);
+}
+
+#[test]
+fn test_explain_payout_matches_resolved_pending() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &30_0000000, &BetSide::Up);
+    client.place_bet(&bob, &70_0000000, &BetSide::Up);
+    client.place_bet(&carol, &33_0000001, &BetSide::Down);
+
+    let final_price: u128 = 1_2000000;
+    let alice_explained = client.explain_payout(&alice, &final_price);
+    let carol_explained = client.explain_payout(&carol, &final_price);
+
+    assert_eq!(alice_explained.stake, 30_0000000);
+    assert_eq!(alice_explained.winning_pool, 100_0000000);
+    assert_eq!(alice_explained.losing_pool, 33_0000001);
+    assert_eq!(
+        alice_explained.final_payout,
+        alice_explained.stake
+            + alice_explained.raw_share
+            + alice_explained.rollover_share
+            + alice_explained.remainder_adjustment
+    );
+    assert_eq!(alice_explained.rollover_share, 0);
+    assert_eq!(carol_explained.final_payout, 0);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: final_price,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert_eq!(
+        client.get_pending_winnings(&alice),
+        alice_explained.final_payout
+    );
+    assert_eq!(
+        client.get_pending_winnings(&carol),
+        carol_explained.final_payout
+    );
+}
+
+#[test]
+fn test_winner_shares_split_losing_pool() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &30_0000000, &BetSide::Up);
+    client.place_bet(&bob, &70_0000000, &BetSide::Up);
+    client.place_bet(&carol, &33_0000001, &BetSide::Down);
+
+    let losing_pool: i128 = 33_0000001;
+    let shares = client.get_winner_shares(&1_2000000);
+    assert_eq!(shares.len(), 2);
+    let total: i128 = shares.iter().map(|(_, share)| share).sum();
+    assert!(total <= losing_pool);
+    assert!(losing_pool - total < shares.len() as i128);
+
+    let alice_share = shares
+        .iter()
+        .find(|(user, _)| *user == alice)
+        .map(|(_, share)| share)
+        .unwrap();
+    assert_eq!(alice_share, 9_9000000);
+
+    // Down winning leaves carol with the whole Up pool as profit
+    let shares = client.get_winner_shares(&8000000);
+    assert_eq!(shares.len(), 1);
+    assert_eq!(shares.get(0).unwrap(), (carol.clone(), 100_0000000));
+
+    // Price unchanged refunds everyone, so nobody takes a share
+    assert_eq!(client.get_winner_shares(&1_0000000).len(), 0);
+}
+
+#[test]
+fn test_carried_pot_reported_apart_from_shares() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_one_sided_policy(&2);
+
+    // A one-sided round carries its 60 vXLM over
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &60_0000000, &BetSide::Up);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_rollover_pot(), 60_0000000);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &40_0000000, &BetSide::Down);
+
+    let final_price: u128 = 1_5000000;
+    let shares = client.get_winner_shares(&final_price);
+    assert_eq!(shares.len(), 1);
+    assert_eq!(shares.get(0).unwrap(), (alice.clone(), 40_0000000));
+
+    let explained = client.explain_payout(&alice, &final_price);
+    assert_eq!(explained.losing_pool, 40_0000000);
+    assert_eq!(explained.raw_share, 40_0000000);
+    assert_eq!(explained.rollover_share, 60_0000000);
+    assert_eq!(explained.remainder_adjustment, 0);
+    assert_eq!(explained.final_payout, 200_0000000);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: final_price,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+
+    assert_eq!(client.get_pending_winnings(&alice), explained.final_payout);
+    assert_eq!(client.get_rollover_pot(), 0);
+    assert!(client.check_solvency());
+}
+
+#[test]
+fn test_house_fee_taken_from_losing_pool() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    assert_eq!(
+        client.try_set_fee_bps(&10_001),
+        Err(Ok(ContractError::InvalidBasisPoints))
+    );
+    client.set_fee_bps(&1_000);
+    assert_eq!(client.get_fee_bps(), 1_000);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // 10% of Bob's 50 goes to the house, Alice gets the other 45
+    assert_eq!(client.get_pending_winnings(&alice), 145_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 0);
+    assert_eq!(client.get_fee_treasury(), 5_0000000);
+    assert_eq!(client.get_protocol_stats().total_fees, 5_0000000);
+}
+
+#[test]
+fn test_net_odds_lower_than_gross_with_fee() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // No round, then an empty one
+    assert_eq!(client.get_net_odds(), (0, 0));
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.get_net_odds(), (0, 0));
+
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    // Up takes back its stake only; nobody is on Down yet
+    assert_eq!(client.get_net_odds(), (10_000, 0));
+
+    client.place_bet(&bob, &50_0000000, &BetSide::Down);
+    let (gross_up, gross_down) = client.get_net_odds();
+    assert_eq!((gross_up, gross_down), (15_000, 30_000));
+
+    client.set_fee_bps(&1_000);
+    let (net_up, net_down) = client.get_net_odds();
+    assert_eq!((net_up, net_down), (14_500, 28_000));
+    assert!(net_up < gross_up);
+    assert!(net_down < gross_down);
+}
+
+#[test]
+fn test_quote_multiplier_shrinks_with_bet_size() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // No round, then an empty one where a bet can only win its stake back
+    assert_eq!(client.quote_multiplier(&BetSide::Up, &10_0000000), 0);
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.quote_multiplier(&BetSide::Up, &10_0000000), 10_000);
+    assert_eq!(client.quote_multiplier(&BetSide::Up, &0), 0);
+
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    // Down is empty: a 10 bet there would take Up's whole 100
+    assert_eq!(
+        client.quote_multiplier(&BetSide::Down, &10_0000000),
+        110_000
+    );
+    assert_eq!(client.quote_multiplier(&BetSide::Up, &10_0000000), 10_000);
+
+    client.place_bet(&bob, &100_0000000, &BetSide::Down);
+    // (110 + 100) / 110 vs (1100 + 100) / 1100
+    let small = client.quote_multiplier(&BetSide::Up, &10_0000000);
+    let large = client.quote_multiplier(&BetSide::Up, &1000_0000000);
+    assert_eq!(small, 19_090);
+    assert_eq!(large, 10_909);
+    assert!(large < small);
+
+    // A tiny quote approaches the current odds
+    assert_eq!(client.get_net_odds().0, 20_000);
+    assert_eq!(client.quote_multiplier(&BetSide::Up, &1), 19_999);
+}
+
+#[test]
+fn test_oracle_ledger_lag_allows_on_time_resolution() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    assert_eq!(client.get_oracle_max_ledger_lag(), 0);
+    client.set_oracle_max_ledger_lag(&10);
+    assert_eq!(client.get_oracle_max_ledger_lag(), 10);
+
+    client.create_round(&1_0000000, &None);
+    let end_ledger = client.get_active_round().unwrap().end_ledger;
+
+    // Exactly at the edge of the window
+    env.ledger().with_mut(|li| {
+        li.sequence_number = end_ledger + 10;
+    });
+
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert!(client.get_active_round().is_none());
+}
+
+#[test]
+fn test_oracle_ledger_lag_rejects_late_resolution() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.set_oracle_max_ledger_lag(&10);
+
+    client.create_round(&1_0000000, &None);
+    let end_ledger = client.get_active_round().unwrap().end_ledger;
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = end_ledger + 11;
+    });
+
+    let payload = OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    };
+    let result = client.try_resolve_round(&payload);
+    assert_eq!(result, Err(Ok(ContractError::OracleTooLate)));
+
+    // Lifting the limit lets the same resolution through
+    client.set_oracle_max_ledger_lag(&0);
+    client.resolve_round(&payload);
+    assert!(client.get_active_round().is_none());
+}
+
+/// Resolves a Precision round where two equal stakes tie, at the given ledger,
+/// using the draw tie-break; returns (alice, bob) pending winnings
+fn resolve_drawn_tie_at(ledger: u32) -> (i128, i128) {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    // Both guesses are within tolerance
+    client.set_precision_tolerance(&Some(5));
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_precision_payout_mode(&2);
+
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2300); // 3 away
+    client.place_precision_prediction(&bob, &100_0000000, &2294); // 3 away
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = ledger;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2297,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    (
+        client.get_pending_winnings(&alice),
+        client.get_pending_winnings(&bob),
+    )
+}
+
+#[test]
+fn test_precision_draw_picks_single_winner_reproducibly() {
+    let outcome = resolve_drawn_tie_at(20);
+
+    // One sole winner takes the whole pot
+    assert!(outcome == (200_0000000, 0) || outcome == (0, 200_0000000));
+
+    // Same ledger state, same winner
+    assert_eq!(resolve_drawn_tie_at(20), outcome);
+}
+
+#[test]
+fn test_precision_draw_changes_with_ledger() {
+    let mut alice_wins = 0;
+    let mut bob_wins = 0;
+    for ledger in 12..32 {
+        match resolve_drawn_tie_at(ledger) {
+            (200_0000000, 0) => alice_wins += 1,
+            (0, 200_0000000) => bob_wins += 1,
+            outcome => panic!("pot was split: {:?}", outcome),
+        }
+    }
+
+    // Different resolution ledgers pick different winners
+    assert!(alice_wins > 0);
+    assert!(bob_wins > 0);
+}
+
+#[test]
+fn test_projected_winner_count_updown() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+
+    // No active round
+    assert_eq!(client.projected_winner_count(&1_5000000), 0);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Up);
+    client.place_bet(&carol, &70_0000000, &BetSide::Down);
+
+    assert_eq!(client.projected_winner_count(&1_5000000), 2);
+    assert_eq!(client.projected_winner_count(&5000000), 1);
+    // Unchanged price refunds everyone
+    assert_eq!(client.projected_winner_count(&1_0000000), 0);
+
+    // Read-only: the round is untouched
+    assert_eq!(client.get_side_totals(), (150_0000000, 70_0000000));
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+}
+
+#[test]
+fn test_projected_winner_count_precision_exact_match() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &10_0000000, &2300);
+    client.place_precision_prediction(&bob, &10_0000000, &2310);
+    client.place_precision_prediction(&carol, &10_0000000, &2300);
+
+    // Alice and Carol both guessed 2300 exactly
+    assert_eq!(client.projected_winner_count(&2300), 2);
+    // Bob alone is exact
+    assert_eq!(client.projected_winner_count(&2310), 1);
+}
+
+/// Three equal UP winners split a losing pool that doesn't divide by three;
+/// returns each winner's pending winnings under the given rounding mode
+fn resolve_uneven_split(rounding: u32) -> [i128; 3] {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let winners = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let loser = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.set_rounding(&rounding);
+    assert_eq!(client.get_rounding(), rounding);
+
+    client.create_round(&1_0000000, &None);
+    for winner in winners.iter() {
+        client.mint_initial(winner);
+        client.place_bet(winner, &30_0000000, &BetSide::Up);
+    }
+    client.mint_initial(&loser);
+    client.place_bet(&loser, &10_0000001, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    [
+        client.get_pending_winnings(&winners[0]),
+        client.get_pending_winnings(&winners[1]),
+        client.get_pending_winnings(&winners[2]),
+    ]
+}
+
+#[test]
+fn test_rounding_floor_vs_nearest() {
+    // Each winner is owed 10_0000001 / 3 = 3_3333333.67 of the losing pool
+    let floor = resolve_uneven_split(0);
+    assert_eq!(floor, [33_3333333; 3]);
+    // Floor leaves 2 stroops of dust with the protocol
+    assert_eq!(floor.iter().sum::<i128>(), 100_0000001 - 2);
+
+    let mut nearest = resolve_uneven_split(1);
+    // Rounded up to 3_3333334, with the 1 stroop overshoot taken back from one winner
+    nearest.sort();
+    assert_eq!(nearest, [33_3333333, 33_3333334, 33_3333334]);
+    assert_eq!(nearest.iter().sum::<i128>(), 100_0000001);
+}
+
+#[test]
+fn test_set_rounding_rejects_unknown_mode() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    assert_eq!(
+        client.try_set_rounding(&2),
+        Err(Ok(ContractError::InvalidMode))
+    );
+    assert_eq!(client.get_rounding(), 0);
+}
+
+#[test]
+fn test_deductions_exceeding_losing_pool_are_rejected() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // 60% consolation, then a fee above 100% that the setter would refuse
+    client.set_consolation_bps(&6000);
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&ConfigKey::FeeBps, &30_000u32);
+    });
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    let payload = OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    };
+    assert_eq!(
+        client.try_resolve_round(&payload),
+        Err(Ok(ContractError::DeductionsExceedPool))
+    );
+
+    // Nothing was paid and the round is still open
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert!(client.get_active_round().is_some());
+
+    // Back within bounds the same resolution goes through
+    client.set_fee_bps(&1000);
+    client.resolve_round(&payload);
+    // 40 left after consolation, 4 of it to the house
+    assert_eq!(client.get_pending_winnings(&alice), 136_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 60_0000000);
+}
+
+/// Resolves a Precision round Alice wins outright (200 vXLM pot) with a 150 vXLM payout cap
+/// Returns (alice pending, fee treasury, rollover pot)
+fn resolve_capped_precision_win(policy: u32) -> (i128, i128, i128) {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_precision_max_payout(&150_0000000);
+    client.set_payout_cap_policy(&policy);
+
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2297);
+    client.place_precision_prediction(&bob, &100_0000000, &2400);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2297,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("capped"), alice.clone()).into_val(&env),
+                50_0000000i128.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("round"), symbol_short!("resolved")).into_val(&env),
+                2297u128.into_val(&env),
+            ),
+        ]
+    );
+
+    (
+        client.get_pending_winnings(&alice),
+        client.get_fee_treasury(),
+        client.get_rollover_pot(),
+    )
+}
+
+#[test]
+fn test_precision_payout_cap_excess_to_treasury() {
+    assert_eq!(
+        resolve_capped_precision_win(0),
+        (150_0000000, 50_0000000, 0)
+    );
+}
+
+#[test]
+fn test_precision_payout_cap_excess_rolls_over() {
+    assert_eq!(
+        resolve_capped_precision_win(1),
+        (150_0000000, 0, 50_0000000)
+    );
+}
+
+#[test]
+fn test_precision_winner_takes_rollover_pot() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Round 0: Alice's 200 win is capped at 150, the other 50 rolls over
+    client.set_precision_max_payout(&150_0000000);
+    client.set_payout_cap_policy(&1);
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2297);
+    client.place_precision_prediction(&bob, &100_0000000, &2400);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2297,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_rollover_pot(), 50_0000000);
+
+    // Round 12: Bob's closest guess takes the 200 pot plus the carried 50
+    client.set_precision_max_payout(&0);
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2297);
+    client.place_precision_prediction(&bob, &100_0000000, &2400);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2400,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+
+    assert_eq!(client.get_pending_winnings(&bob), 250_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 150_0000000);
+    assert_eq!(client.get_rollover_pot(), 0);
+    assert!(client.check_solvency());
+}
+
+#[test]
+fn test_precision_payout_cap_settings() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    assert_eq!(client.get_precision_max_payout(), 0);
+    assert_eq!(client.get_payout_cap_policy(), 0);
+
+    assert_eq!(
+        client.try_set_precision_max_payout(&-1),
+        Err(Ok(ContractError::InvalidBetAmount))
+    );
+    assert_eq!(
+        client.try_set_payout_cap_policy(&2),
+        Err(Ok(ContractError::InvalidMode))
+    );
+}
+
+#[test]
+fn test_precision_zone_winners_split_pot_by_stake() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+
+    // Zones: below 2290, [2290, 2300), 2300 and up
+    client.set_precision_zones(&vec![&env, 2290u128, 2300u128]);
+    client.set_precision_payout_mode(&3);
+
+    client.create_round(&2297, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2291);
+    client.place_precision_prediction(&bob, &300_0000000, &2299);
+    client.place_precision_prediction(&carol, &100_0000000, &2300); // closest, wrong zone
+
+    // Zones are fixed while the round is open
+    assert_eq!(
+        client.try_set_precision_zones(&vec![&env, 2000u128]),
+        Err(Ok(ContractError::RoundAlreadyActive))
+    );
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2297,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // 500 pot split 1:3 between the two guesses in the winning zone
+    assert_eq!(client.get_pending_winnings(&alice), 125_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 375_0000000);
+    assert_eq!(client.get_pending_winnings(&carol), 0);
+    assert_eq!(client.get_user_stats(&carol).total_losses, 1);
+}
+
+#[test]
+fn test_precision_zone_validation() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    assert_eq!(
+        client.try_set_precision_payout_mode(&3),
+        Err(Ok(ContractError::ZoneNotConfigured))
+    );
+    assert_eq!(
+        client.try_set_precision_zones(&vec![&env, 2300u128, 2290u128]),
+        Err(Ok(ContractError::InvalidPrice))
+    );
+    assert_eq!(
+        client.try_set_precision_zones(&vec![&env, 0u128]),
+        Err(Ok(ContractError::InvalidPrice))
+    );
+
+    client.set_precision_zones(&vec![&env, 2290u128, 2300u128]);
+    assert_eq!(client.get_precision_zones(), vec![&env, 2290u128, 2300u128]);
+    client.set_precision_payout_mode(&3);
+
+    // Zones in use can't be cleared
+    assert_eq!(
+        client.try_set_precision_zones(&Vec::new(&env)),
+        Err(Ok(ContractError::ZoneNotConfigured))
+    );
+    client.set_precision_payout_mode(&0);
+    client.set_precision_zones(&Vec::new(&env));
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("config"), symbol_short!("zones")).into_val(&env),
+                Vec::<u128>::new(&env).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.get_precision_zones().len(), 0);
+}
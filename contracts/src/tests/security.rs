@@ -2,7 +2,7 @@
 
 use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
 use crate::errors::ContractError;
-use crate::types::OraclePayload;
+use crate::types::{BetSide, OraclePayload};
 use soroban_sdk::{
     testutils::{Address as _, Ledger as _},
     Address, Env,
@@ -94,3 +94,96 @@ fn test_resolve_round_valid_payload() {
     client.resolve_round(&payload);
     assert_eq!(client.get_active_round(), None);
 }
+
+#[test]
+fn test_resolve_round_resubmission_already_resolved() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+
+    let payload = OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    };
+    client.resolve_round(&payload);
+
+    // Relayer resubmits the same resolution
+    let result = client.try_resolve_round(&payload);
+    assert_eq!(result, Err(Ok(ContractError::AlreadyResolved)));
+
+    // Still rejected once the next round is open
+    client.create_round(&1_0000000, &None);
+    let result = client.try_resolve_round(&payload);
+    assert_eq!(result, Err(Ok(ContractError::AlreadyResolved)));
+
+    // Unknown round ids are still a plain mismatch
+    let result = client.try_resolve_round(&OraclePayload {
+        round_id: 999,
+        ..payload
+    });
+    assert_eq!(result, Err(Ok(ContractError::InvalidOracleRound)));
+}
+
+#[test]
+fn test_oracle_cannot_bet_unless_allowed() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&oracle);
+    assert!(!client.get_oracle_can_bet());
+
+    client.create_round(&1_0000000, &None);
+    assert_eq!(
+        client.try_place_bet(&oracle, &10_0000000, &BetSide::Up),
+        Err(Ok(ContractError::OracleCannotBet))
+    );
+
+    client.set_oracle_can_bet(&true);
+    assert!(client.get_oracle_can_bet());
+    client.place_bet(&oracle, &10_0000000, &BetSide::Up);
+    assert!(client.has_bet(&oracle));
+}
+
+#[test]
+fn test_oracle_cannot_predict_unless_allowed() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&oracle);
+
+    client.create_round(&2297, &Some(1));
+    assert_eq!(
+        client.try_place_precision_prediction(&oracle, &10_0000000, &2300),
+        Err(Ok(ContractError::OracleCannotBet))
+    );
+
+    client.set_oracle_can_bet(&true);
+    client.place_precision_prediction(&oracle, &10_0000000, &2300);
+    assert!(client.has_bet(&oracle));
+}
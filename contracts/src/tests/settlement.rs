@@ -0,0 +1,206 @@
+//! Tests for resolving a round and settling its payouts in batches.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::errors::ContractError;
+use crate::types::{BetSide, OraclePayload};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    Address, Env, Vec,
+};
+
+#[test]
+fn test_settle_round_in_two_batches() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &50_0000000, &BetSide::Up);
+    client.place_bet(&carol, &150_0000000, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round_batched(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Round is closed but nobody has been paid yet
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_settlement_progress(), (0, 3));
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert_eq!(client.get_pending_winnings(&bob), 0);
+
+    assert_eq!(client.settle_batch(&2), 2);
+    assert_eq!(client.get_settlement_progress(), (2, 3));
+
+    // No new round until every payout is applied
+    let result = client.try_create_round(&1_0000000, &None);
+    assert_eq!(result, Err(Ok(ContractError::SettlementInProgress)));
+
+    // Carol's losing position counts towards the limit too
+    assert_eq!(client.settle_batch(&2), 1);
+    assert_eq!(client.get_settlement_progress(), (0, 0));
+
+    // Same result as a one-shot resolution
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 100_0000000);
+    assert_eq!(client.get_pending_winnings(&carol), 0);
+    assert_eq!(client.get_user_stats(&alice).total_wins, 1);
+    assert_eq!(client.get_user_stats(&carol).total_losses, 1);
+
+    // Nothing left to apply
+    assert_eq!(client.settle_batch(&2), 0);
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+
+    client.create_round(&1_0000000, &None);
+}
+
+#[test]
+fn test_settle_round_too_large_for_one_shot_resolution() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+
+    // Crediting 30 users in one resolve_round writes more ledger entries than a
+    // transaction may; split up, every batch stays within the limits
+    let mut up_bettors = Vec::new(&env);
+    let mut down_bettors = Vec::new(&env);
+    for i in 0..30 {
+        let user = Address::generate(&env);
+        client.mint_initial(&user);
+        if i % 2 == 0 {
+            client.place_bet(&user, &10_0000000, &BetSide::Up);
+            up_bettors.push_back(user);
+        } else {
+            client.place_bet(&user, &10_0000000, &BetSide::Down);
+            down_bettors.push_back(user);
+        }
+    }
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round_batched(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_settlement_progress(), (0, 30));
+
+    assert_eq!(client.settle_batch(&10), 10);
+    assert_eq!(client.settle_batch(&10), 10);
+    assert_eq!(client.settle_batch(&10), 10);
+    assert_eq!(client.settle_batch(&10), 0);
+    assert_eq!(client.get_settlement_progress(), (0, 0));
+
+    for user in up_bettors.iter() {
+        assert_eq!(client.get_pending_winnings(&user), 20_0000000);
+    }
+    for user in down_bettors.iter() {
+        assert_eq!(client.get_pending_winnings(&user), 0);
+    }
+    assert!(client.check_solvency());
+}
+
+#[test]
+fn test_resolve_round_batched_rejects_precision_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.create_round(&1_0000000, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2300);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    let result = client.try_resolve_round_batched(&OraclePayload {
+        price: 2300,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(result, Err(Ok(ContractError::WrongModeForPrediction)));
+}
+
+#[test]
+fn test_settle_batch_takes_rounding_excess_back_once() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let loser = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.set_rounding(&1);
+    client.create_round(&1_0000000, &None);
+
+    let mut winners = Vec::new(&env);
+    for _ in 0..3 {
+        let winner = Address::generate(&env);
+        client.mint_initial(&winner);
+        client.place_bet(&winner, &30_0000000, &BetSide::Up);
+        winners.push_back(winner);
+    }
+    client.mint_initial(&loser);
+    client.place_bet(&loser, &10_0000001, &BetSide::Down);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round_batched(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // One position per batch; the 1 stroop overshoot still comes off a single winner
+    while client.settle_batch(&1) > 0 {}
+
+    let mut total: i128 = 0;
+    let mut rounded_down = 0;
+    for winner in winners.iter() {
+        let pending = client.get_pending_winnings(&winner);
+        if pending == 33_3333333 {
+            rounded_down += 1;
+        } else {
+            assert_eq!(pending, 33_3333334);
+        }
+        total += pending;
+    }
+    assert_eq!(rounded_down, 1);
+    assert_eq!(total, 100_0000001);
+    assert!(client.check_solvency());
+}
@@ -0,0 +1,220 @@
+//! Tests for maintenance functions behind the `testnet` feature.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+#[cfg(feature = "testnet")]
+#[test]
+fn test_reset_contract_allows_reinitialize() {
+    use crate::errors::ContractError;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&user);
+    client.set_windows(&10, &20);
+    client.set_max_pot(&500_0000000);
+    client.create_round(&1_0000000, &None);
+
+    assert_eq!(
+        client.try_initialize(&admin, &oracle),
+        Err(Ok(ContractError::AlreadyInitialized))
+    );
+
+    client.reset_contract();
+
+    assert!(!client.is_initialized());
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_max_pot(), 0);
+    assert_eq!(client.preview_windows(&0), (0, 6, 12));
+
+    // Balances survive the reset
+    assert_eq!(client.balance(&user), 1000_0000000);
+
+    let new_oracle = Address::generate(&env);
+    client.initialize(&admin, &new_oracle);
+    assert_eq!(client.get_oracle(), Some(new_oracle));
+
+    // The reset round's id stays used up
+    assert_eq!(client.get_last_round_id(), Some(0));
+    assert_eq!(
+        client.try_create_round(&1_0000000, &None),
+        Err(Ok(ContractError::RoundIdTaken))
+    );
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 1;
+    });
+    client.create_round(&1_0000000, &None);
+}
+
+#[cfg(feature = "testnet")]
+#[test]
+fn test_reset_contract_refunds_live_bets() {
+    use crate::errors::ContractError;
+    use crate::types::{BetSide, OraclePayload};
+    use soroban_sdk::testutils::Ledger as _;
+
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &40_0000000, &BetSide::Down);
+
+    client.reset_contract();
+
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 40_0000000);
+    assert!(client.check_solvency());
+
+    // A batched settlement has to be finished before the payouts can be dropped
+    client.initialize(&admin, &oracle);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 20;
+    });
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &10_0000000, &BetSide::Up);
+    client.place_bet(&bob, &10_0000000, &BetSide::Down);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 32;
+    });
+    client.resolve_round_batched(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 20,
+    });
+    assert_eq!(
+        client.try_reset_contract(),
+        Err(Ok(ContractError::SettlementInProgress))
+    );
+    client.settle_batch(&10);
+    client.reset_contract();
+    assert!(client.check_solvency());
+}
+
+#[cfg(not(feature = "testnet"))]
+#[test]
+fn test_reset_contract_absent_without_feature() {
+    use soroban_sdk::{vec, InvokeError, Symbol};
+
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    let result = env.try_invoke_contract::<(), InvokeError>(
+        &contract_id,
+        &Symbol::new(&env, "reset_contract"),
+        vec![&env],
+    );
+    assert!(result.is_err());
+    assert!(client.is_initialized());
+}
+
+#[cfg(feature = "testnet")]
+#[test]
+fn test_dump_state_reflects_current_state() {
+    use crate::types::{BetSide, OraclePayload, RoundState};
+    use soroban_sdk::testutils::Ledger as _;
+
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    // Uninitialized: no roles, no round, defaults everywhere
+    let dump = client.dump_state();
+    assert_eq!(dump.admin, None);
+    assert_eq!(dump.oracle, None);
+    assert_eq!(dump.active_round, RoundState::None);
+    assert_eq!(dump.fee_bps, 0);
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_windows(&10, &20);
+    client.set_fee_bps(&250);
+    client.set_max_pot(&500_0000000);
+    client.set_rounding(&1);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down);
+
+    let dump = client.dump_state();
+    assert_eq!(dump.admin, Some(admin.clone()));
+    assert_eq!(dump.oracle, Some(oracle.clone()));
+    assert_eq!(
+        dump.active_round,
+        RoundState::Active(client.get_active_round().unwrap())
+    );
+    assert_eq!(dump.bet_window_ledgers, 10);
+    assert_eq!(dump.run_window_ledgers, 20);
+    assert_eq!(dump.fee_bps, 250);
+    assert_eq!(dump.max_pot, 500_0000000);
+    assert_eq!(dump.rounding, 1);
+    assert_eq!(dump.pending_total, 0);
+    assert_eq!(dump.total_supply, 2000_0000000);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 20;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    let dump = client.dump_state();
+    assert_eq!(dump.active_round, RoundState::None);
+    assert_eq!(dump.pending_total, client.get_pending_total());
+    assert_eq!(dump.fee_treasury, 2_5000000);
+}
+
+#[cfg(not(feature = "testnet"))]
+#[test]
+fn test_dump_state_absent_without_feature() {
+    use soroban_sdk::{vec, InvokeError, Symbol, Val};
+
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+
+    let result = env.try_invoke_contract::<Val, InvokeError>(
+        &contract_id,
+        &Symbol::new(&env, "dump_state"),
+        vec![&env],
+    );
+    assert!(result.is_err());
+}
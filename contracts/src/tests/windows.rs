@@ -4,8 +4,9 @@ use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
 use crate::errors::ContractError;
 use crate::types::{BetSide, OraclePayload};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger as _},
-    Address, Env, IntoVal,
+    symbol_short,
+    testutils::{Address as _, Events, Ledger as _},
+    vec, Address, Env, IntoVal,
 };
 
 #[test]
@@ -132,6 +133,29 @@ fn test_set_windows_bet_must_be_less_than_run() {
     client.set_windows(&6, &12);
 }
 
+#[test]
+fn test_set_windows_run_ledgers_upper_bound() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    let result = client.try_set_windows(&6, &100_001);
+    assert_eq!(result, Err(Ok(ContractError::InvalidDuration)));
+
+    // Boundary value is accepted
+    client.set_windows(&6, &100_000);
+
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.get_active_round().unwrap().end_ledger, 100_000);
+}
+
 #[test]
 fn test_create_round_uses_configured_windows() {
     let env = Env::default();
@@ -193,6 +217,60 @@ fn test_create_round_uses_default_windows() {
     assert_eq!(round.end_ledger, 62); // 50 + 12
 }
 
+#[test]
+fn test_set_windows_min_bet_ledgers() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    assert_eq!(client.get_min_bet_ledgers(), 2);
+
+    // Default minimum of 2
+    let result = client.try_set_windows(&1, &12);
+    assert_eq!(result, Err(Ok(ContractError::InvalidDuration)));
+    client.set_windows(&2, &12);
+
+    // Raised minimum
+    client.set_min_bet_ledgers(&5);
+    let result = client.try_set_windows(&4, &12);
+    assert_eq!(result, Err(Ok(ContractError::InvalidDuration)));
+    client.set_windows(&5, &12);
+
+    assert_eq!(
+        client.try_set_min_bet_ledgers(&0),
+        Err(Ok(ContractError::InvalidDuration))
+    );
+}
+
+#[test]
+fn test_preview_windows() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    // Defaults before any windows are configured
+    assert_eq!(client.preview_windows(&100), (100, 106, 112));
+
+    client.set_windows(&10, &20);
+    assert_eq!(client.preview_windows(&100), (100, 110, 120));
+
+    // Preview does not create a round
+    assert_eq!(client.get_active_round(), None);
+}
+
 #[test]
 fn test_betting_closes_at_bet_end_ledger() {
     let env = Env::default();
@@ -239,6 +317,134 @@ fn test_betting_closes_at_bet_end_ledger() {
     assert_eq!(result, Err(Ok(ContractError::RoundEnded)));
 }
 
+#[test]
+fn test_close_betting_early() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    assert_eq!(
+        client.try_close_betting(),
+        Err(Ok(ContractError::NoActiveRound))
+    );
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 2;
+    });
+    client.close_betting();
+
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.bet_end_ledger, 2);
+    assert_eq!(round.end_ledger, 12);
+
+    let result = client.try_place_bet(&bob, &100_0000000, &BetSide::Down);
+    assert_eq!(result, Err(Ok(ContractError::RoundEnded)));
+
+    // Resolution still waits for end_ledger
+    let payload = OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    };
+    assert_eq!(
+        client.try_resolve_round(&payload),
+        Err(Ok(ContractError::RoundNotEnded))
+    );
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&payload);
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+}
+
+#[test]
+fn test_reopen_betting_after_early_close() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+
+    assert_eq!(
+        client.try_reopen_betting(&8),
+        Err(Ok(ContractError::NoActiveRound))
+    );
+
+    client.create_round(&1_0000000, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 2;
+    });
+    client.close_betting();
+
+    client.reopen_betting(&8);
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.bet_end_ledger, 8);
+    assert_eq!(round.end_ledger, 12);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 7;
+    });
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+}
+
+#[test]
+fn test_reopen_betting_rejects_out_of_range_ledgers() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 4;
+    });
+    client.close_betting();
+
+    // At or past end_ledger
+    for ledger in [12u32, 20] {
+        assert_eq!(
+            client.try_reopen_betting(&ledger),
+            Err(Ok(ContractError::InvalidDuration))
+        );
+    }
+    // Not in the future
+    for ledger in [3u32, 4] {
+        assert_eq!(
+            client.try_reopen_betting(&ledger),
+            Err(Ok(ContractError::InvalidDuration))
+        );
+    }
+
+    assert_eq!(client.get_active_round().unwrap().bet_end_ledger, 4);
+}
+
 #[test]
 fn test_resolution_only_allowed_after_run_ledgers() {
     let env = Env::default();
@@ -383,3 +589,203 @@ fn test_place_precision_prediction_fails_without_user_auth() {
     let result = client.try_place_precision_prediction(&user, &100_0000000, &2297);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_ledgers_until_resolvable() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    // No active round
+    assert_eq!(client.ledgers_until_resolvable(), None);
+
+    // Round created at ledger 0 with default run window of 12
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.ledgers_until_resolvable(), Some(12));
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 8;
+    });
+    assert_eq!(client.ledgers_until_resolvable(), Some(4));
+
+    // At and past end_ledger the round is resolvable
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    assert_eq!(client.ledgers_until_resolvable(), Some(0));
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 20;
+    });
+    assert_eq!(client.ledgers_until_resolvable(), Some(0));
+}
+
+#[test]
+fn test_set_windows_emits_event() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.set_windows(&10, &20);
+
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("windows"), symbol_short!("updated")).into_val(&env),
+                (10u32, 20u32).into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_config_setters_emit_events() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+
+    client.set_max_pot(&500_0000000);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("config"), symbol_short!("max_pot")).into_val(&env),
+                500_0000000i128.into_val(&env),
+            ),
+        ]
+    );
+
+    client.set_daily_limit(&100_0000000, &720);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("config"), symbol_short!("day_limit")).into_val(&env),
+                (100_0000000i128, 720u32).into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_extend_round_moves_resolution_point() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.mint_initial(&alice);
+
+    assert_eq!(
+        client.try_extend_round(&5),
+        Err(Ok(ContractError::NoActiveRound))
+    );
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+
+    // Betting is still open, so its close moves too
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 3;
+    });
+    client.extend_round(&5);
+
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.bet_end_ledger, 11);
+    assert_eq!(round.end_ledger, 17);
+
+    // The old end is no longer resolvable
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    let payload = OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    };
+    assert_eq!(
+        client.try_resolve_round(&payload),
+        Err(Ok(ContractError::RoundNotEnded))
+    );
+
+    // Betting already closed: only the end moves
+    client.extend_round(&3);
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.bet_end_ledger, 11);
+    assert_eq!(round.end_ledger, 20);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 20;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+}
+
+#[test]
+fn test_extend_round_rejects_zero_and_too_long() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle);
+    client.create_round(&1_0000000, &None);
+
+    assert_eq!(
+        client.try_extend_round(&0),
+        Err(Ok(ContractError::InvalidDuration))
+    );
+    assert_eq!(
+        client.try_extend_round(&100_000),
+        Err(Ok(ContractError::InvalidDuration))
+    );
+    assert_eq!(
+        client.try_extend_round(&u32::MAX),
+        Err(Ok(ContractError::Overflow))
+    );
+
+    // Up to the maximum round length is fine
+    client.extend_round(&(100_000 - 12));
+    assert_eq!(client.get_active_round().unwrap().end_ledger, 100_000);
+}
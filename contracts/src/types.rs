@@ -1,6 +1,6 @@
 //! Type definitions for the XLM Price Prediction Market.
 
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, Vec};
 
 /// Round mode for prediction type
 #[contracttype]
@@ -24,11 +24,84 @@ pub enum DataKey {
     PrecisionPositions, // Map<Address, PrecisionPrediction> for Precision mode
     PendingWinnings(Address),
     UserStats(Address),
-    BetWindowLedgers, // Bet window duration in ledgers
-    RunWindowLedgers, // Run window duration in ledgers
+    BetWindowLedgers,         // Bet window duration in ledgers
+    RunWindowLedgers,         // Run window duration in ledgers
+    FeeTreasury,              // vXLM accrued by the protocol (swept winnings, fees)
+    TotalRounds,              // Number of rounds resolved
+    TotalVolume,              // Total vXLM wagered across all rounds
+    TotalFees,                // Total vXLM ever credited to the fee treasury
+    Checkpoints(u32),         // Vec<(price, timestamp)> oracle samples for a round
+    VolumeWindow(Address),    // (window_start_ledger, staked) for the user's rolling limit
+    LastResolvedLedger,       // Ledger at which the last round was resolved
+    BetRecord(Address, u32),  // BetRecord for a user in a round (keyed by round_id)
+    Settlement,               // Round resolved in batches whose positions are not all paid yet
+    SettlementPositions,      // Map<Address, UserPosition> of the round being settled in batches
+    Blacklist(Address),       // Present when the address may not mint or bet
+    TotalClaimed(Address),    // Lifetime winnings claimed by the user
+    UserRounds(Address),      // Vec<u32> of round ids the user bet in (most recent kept)
+    Resolved(u32),            // Present once the round with this id has been resolved
+    Season,                   // Current leaderboard season
+    SeasonWins(Address, u32), // Wins by a user in a season
+    Leaderboard(u32),         // Vec<LeaderboardEntry> top players of a season
+    PendingTotal,             // Sum of all users' outstanding pending winnings
+    ResolvedRound,            // Just-resolved round kept for the results reveal
+    RoundResult(u32),         // Outcome of the n-th resolved round (0-based)
+    Collateral,               // Backing asset held for deposited vXLM
+    TotalSupply,              // vXLM minted (mint_initial, deposit, unlocked grants) minus burned
+    TotalBalances,            // Sum of all users' vXLM balances
+    Locked(Address),          // LockedGrant not yet spendable by the user
+    RolloverPot,              // vXLM carried over from one-sided rounds to the next winners
+    Streaks,        // Vec<(Address, streak)> longest current winning streaks, longest first
+    ScheduledRound, // ScheduledRound waiting for activate_scheduled_round
+    BiggestPot,     // Largest total stake of any resolved round
+    AutoClaim(Address), // Present when place_bet should claim the user's winnings first
+    HistoryStart,   // Oldest RoundResult id not yet pruned by the history limit
+    SavingsSplitBps(Address), // Share of each claim the user routes to savings, in basis points
+    Savings(Address), // Claimed winnings set aside by the user's savings split
+    TotalSavings,   // Sum of all users' savings
+    LastCheckpoint, // (price, timestamp) of the latest checkpoint in any round
+    TotalUsers,     // Distinct users who have received the mint_initial grant
+    LastRoundId,    // Id (start ledger) of the most recently opened round
+    PendingByRound(Address), // Map<u32, (amount, first credit ledger)> of pending winnings
+}
+
+/// Storage keys for admin-tunable settings
+///
+/// Kept apart from `DataKey`, which the contract spec caps at 50 variants.
+#[contracttype]
+#[derive(Clone)]
+#[rustfmt::skip]
+pub enum ConfigKey {
+    ClaimDeadlineLedgers,   // Ledgers after resolution before winnings expire (0 = never)
+    DrawBandBps,            // Up/Down refund band around the start price, in basis points
+    MaxPot,                 // Maximum total stake per round (0 = unlimited)
+    DailyLimit,             // (amount, period_ledgers) per-user staking limit
+    RoundCooldown,          // Minimum ledgers between a resolution and the next round
+    MinParticipants,        // Rounds with fewer participants refund everyone
+    ConsolationBps,         // Share of their stake Up/Down losers get back, in basis points
+    PrecisionPayoutMode,    // 0 = closest wins the pot, 1 = inverse-distance shares
+    DefaultMode,            // Round mode create_round uses when none is given
+    MinBetLedgers,          // Shortest bet window set_windows accepts
+    ResultRetention,        // Ledgers a resolved round stays readable (0 = cleared at once)
+    MaxUserShareBps,        // Largest share of a round's pot one user may hold (0 = unlimited)
+    FeeBps,                 // House fee on the Up/Down losing pool, in basis points
+    OneSidedPolicy,         // 0 = settle as usual, 1 = refund minus fee, 2 = roll the pot over
+    PrecisionTolerance,     // Max distance of a winning Precision guess (unset = exact only)
+    PrecisionDistanceMode,  // 0 = absolute stroops, 1 = basis points of the final price
+    OracleMaxLedgerLag,     // Ledgers past end_ledger the oracle may resolve in (0 = no limit)
+    Rounding,               // Up/Down share rounding: 0 = floor, 1 = nearest
+    HistoryLimit,           // Most recent round results kept (default DEFAULT_HISTORY_LIMIT)
+    PrecisionMaxPayout,     // Most one Precision winner may receive (0 = no cap)
+    PayoutCapPolicy,        // Where a capped payout's excess goes: 0 = treasury, 1 = rollover pot
+    RequireFreshStartPrice, // New rounds' start price must be within 1% of the latest checkpoint
+    PrecisionZones,         // Vec<u128> ascending boundaries splitting prices into zones
+    OracleCanBet,           // Lets the oracle address bet and predict (default false)
 }
 
 /// Represents which side a user bet on
+///
+/// Sides are only ever compared as enum values, never as integers; any other
+/// encoding is rejected when the arguments are decoded, before the contract runs.
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub enum BetSide {
@@ -41,6 +114,45 @@ pub enum BetSide {
 pub struct UserPosition {
     pub amount: i128,
     pub side: BetSide,
+    pub round_id: u32, // start_ledger of the round this position belongs to
+}
+
+/// A position as v1 stored it under `DataKey::Positions`, before `round_id` existed
+#[contracttype(export = false)]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct LegacyUserPosition {
+    pub amount: i128,
+    pub side: BetSide,
+}
+
+/// A user's stake in a specific round, kept after the round is resolved
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BetRecord {
+    pub round_id: u32,
+    pub amount: i128,
+    pub choice: BetChoice,
+}
+
+/// Outcome of a resolved round, kept for the results archive
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundResult {
+    pub round_id: u32,
+    pub mode: RoundMode,
+    pub price_start: u128,
+    pub final_price: u128,
+    pub pool_up: i128,
+    pub pool_down: i128,
+    pub resolved_ledger: u32,
+}
+
+/// What a recorded bet was placed on
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum BetChoice {
+    Side(BetSide), // Up/Down mode
+    Price(u128),   // Precision mode predicted price
 }
 
 #[contracttype]
@@ -50,6 +162,134 @@ pub struct UserStats {
     pub total_losses: u32,
     pub current_streak: u32,
     pub best_streak: u32,
+    pub biggest_win: i128, // Largest single payout credited as a win (refunds excluded)
+    pub total_staked: i128, // Lifetime vXLM staked across every bet and season
+    pub total_bets: u32,   // Lifetime number of bets and predictions placed
+}
+
+/// Promotional vXLM granted to a user that can't be bet before unlock_ledger
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LockedGrant {
+    pub amount: i128,
+    pub unlock_ledger: u32,
+}
+
+/// A user's stake in the active round, if they have one
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum CurrentPosition {
+    None,
+    Side(UserPosition),         // Up/Down mode
+    Price(PrecisionPrediction), // Precision mode
+}
+
+/// Everything a profile page shows about a user, read in one call
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccountSnapshot {
+    pub balance: i128,
+    pub pending_winnings: i128,
+    pub current_position: CurrentPosition,
+    pub stats: UserStats,
+}
+
+/// How a position ended when its round was resolved
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+#[repr(u32)]
+pub enum PayoutKind {
+    Win = 0,      // Credited stake plus share of the losing pool
+    Loss = 1,     // Counts as a loss; amount is any consolation credited
+    Refund = 2,   // Stake credited back (price unchanged)
+    Fee = 3,      // House fee taken from the losing pool, credited to the treasury
+    Rollover = 4, // Carried over to the next round's winners instead of paid out now
+}
+
+/// Payouts worked out for a round, and how much of the carried-over pot they hand out
+pub(crate) struct RoundPayouts {
+    pub payouts: Vec<Payout>,
+    pub rollover_paid: i128, // Already included in the Win amounts
+}
+
+/// Amount owed to a user once their round is resolved
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Payout {
+    pub user: Address,
+    pub amount: i128,
+    pub kind: PayoutKind,
+}
+
+/// Inputs and result of a user's Up/Down payout at a given final price
+///
+/// `final_payout = stake + raw_share + rollover_share + remainder_adjustment`. Refunds
+/// report empty pools; a loser's adjustment is their consolation minus their stake.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PayoutExplanation {
+    pub stake: i128,
+    pub winning_pool: i128,
+    pub losing_pool: i128, // What winners split, after loser consolation and the house fee
+    pub raw_share: i128,   // stake * losing_pool / winning_pool, rounded down
+    pub rollover_share: i128, // stake * carried-over pot / winning_pool, rounded down
+    pub remainder_adjustment: i128,
+    pub final_payout: i128,
+}
+
+/// How the positions of a round settled in batches are paid
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum SettlementOutcome {
+    Refund,       // Stakes back, less refund_fee_bps
+    Win(BetSide), // That side splits share_pool pro rata; the other side gets its consolation
+    Void,         // The stakes were carried over at resolution; positions are paid nothing
+}
+
+/// An Up/Down round resolved by `resolve_round_batched` whose positions are paid over `settle_batch` calls
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settlement {
+    pub round_id: u32,
+    pub final_price: u128,
+    pub outcome: SettlementOutcome,
+    pub pool_up: i128,
+    pub pool_down: i128,
+    pub share_pool: i128, // What winners split after consolation, the house fee and the carried pot
+    pub excess: i128, // Round-to-nearest overshoot still to take back from the remaining winners
+    pub consolation_bps: u32,
+    pub refund_fee_bps: u32,
+    pub nearest: bool, // Shares round to nearest instead of down
+    pub unpaid: i128,  // vXLM still owed to the positions not paid yet
+    pub settled: u32,  // Positions paid so far, counted from the end of SettlementPositions
+    pub total: u32,
+}
+
+/// A ranked player on a season leaderboard
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LeaderboardEntry {
+    pub user: Address,
+    pub wins: u32,
+}
+
+/// Built-in defaults clients would otherwise hardcode
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Defaults {
+    pub bet_window_ledgers: u32, // Bet window when set_windows has not been called
+    pub run_window_ledgers: u32, // Run window when set_windows has not been called
+    pub initial_mint: i128,      // vXLM granted by mint_initial
+    pub oracle_max_age_secs: u64, // Oldest oracle timestamp accepted, in seconds before now
+}
+
+/// Aggregate protocol numbers for dashboards
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProtocolStats {
+    pub total_rounds: u32,
+    pub total_volume: i128,
+    pub total_fees: i128,
 }
 
 /// Precision prediction entry (user address + predicted price)
@@ -70,14 +310,75 @@ pub struct OraclePayload {
     pub round_id: u32,
 }
 
+/// Round parameters registered by schedule_round, opened by activate_scheduled_round
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScheduledRound {
+    pub start_price: u128, // Starting price the round will open with
+    pub mode: u32,         // 0 = Up/Down, 1 = Precision
+    pub start_ledger: u32, // Earliest ledger at which it can be activated
+}
+
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub struct Round {
-    pub price_start: u128,   // Starting XLM price in stroops
-    pub start_ledger: u32,   // Ledger when round was created
-    pub bet_end_ledger: u32, // Ledger when betting closes
-    pub end_ledger: u32,     // Ledger when round ends (~5s per ledger)
-    pub pool_up: i128,       // Total vXLM bet on UP
-    pub pool_down: i128,     // Total vXLM bet on DOWN
-    pub mode: RoundMode,     // Round mode: UpDown (0) or Precision (1)
+    pub price_start: u128,      // Starting XLM price in stroops
+    pub start_ledger: u32,      // Ledger when round was created
+    pub bet_end_ledger: u32,    // Ledger when betting closes
+    pub end_ledger: u32,        // Ledger when round ends (~5s per ledger)
+    pub pool_up: i128,          // Total vXLM bet on UP
+    pub pool_down: i128,        // Total vXLM bet on DOWN
+    pub mode: RoundMode,        // Round mode: UpDown (0) or Precision (1)
+    pub created_timestamp: u64, // Ledger timestamp when round was created
+    pub bet_end_timestamp: u64, // Estimated betting close time (advisory, ~5s per ledger)
+    pub end_timestamp: u64,     // Estimated round end time (advisory, ~5s per ledger)
+    pub resolved: bool,         // Set once the oracle price has been applied
+    pub final_price: u128,      // Resolution price (0 until resolved)
+}
+
+/// What get_active_round currently returns
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum RoundStatus {
+    None,     // No round
+    Open,     // Taking bets
+    Closed,   // Betting over, waiting for the oracle price
+    Resolved, // Finished; kept for the results reveal
+}
+
+/// The active round in a state dump, if there is one
+#[cfg(feature = "testnet")]
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum RoundState {
+    None,
+    Active(Round),
+}
+
+/// Roles, active round, pending total and settings read in one call (testnet only)
+#[cfg(feature = "testnet")]
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateDump {
+    pub admin: Option<Address>,
+    pub oracle: Option<Address>,
+    pub active_round: RoundState,
+    pub pending_total: i128,
+    pub fee_treasury: i128,
+    pub total_supply: i128,
+    pub bet_window_ledgers: u32,
+    pub run_window_ledgers: u32,
+    pub default_mode: u32,
+    pub max_pot: i128,
+    pub fee_bps: u32,
+    pub consolation_bps: u32,
+    pub draw_band_bps: u32,
+    pub max_user_share_bps: u32,
+    pub min_participants: u32,
+    pub round_cooldown: u32,
+    pub claim_deadline: u32,
+    pub precision_payout_mode: u32,
+    pub one_sided_policy: u32,
+    pub rounding: u32,
+    pub oracle_max_ledger_lag: u32,
 }